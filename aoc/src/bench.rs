@@ -0,0 +1,149 @@
+use crate::days::Timings;
+use common::runner::format_duration;
+use std::collections::HashMap;
+use std::fs::{read_to_string, write};
+use std::ops::AddAssign;
+use std::path::Path;
+use std::time::Duration;
+
+const STAGES: [&str; 3] = ["parse", "part1", "part2"];
+
+/// Timings of every benchmarked day, paired with the day number
+pub type Report = Vec<(u32, Timings)>;
+
+impl Timings {
+    fn stages(&self) -> [Duration; 3] {
+        [self.parse, self.part1, self.part2]
+    }
+
+    fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+impl AddAssign for Timings {
+    fn add_assign(&mut self, other: Self) {
+        self.parse += other.parse;
+        self.part1 += other.part1;
+        self.part2 += other.part2;
+    }
+}
+
+pub fn print_table(report: &Report) {
+    println!(
+        "{:<5} {:>10} {:>10} {:>10} {:>10}",
+        "Day", "Parse", "Part 1", "Part 2", "Total"
+    );
+    for (day, timings) in report {
+        let [parse, part1, part2] = timings.stages();
+        println!(
+            "{:<5} {:>10} {:>10} {:>10} {:>10}",
+            format!("{:02}", day),
+            format_duration(parse),
+            format_duration(part1),
+            format_duration(part2),
+            format_duration(timings.total()),
+        );
+    }
+    let total = report.iter().map(|(_, timings)| timings.total()).sum();
+    println!("{:<5} {:>43}", "Total", format_duration(total));
+}
+
+/// Saves the report as tab-separated values with one row per day and the
+/// time of each stage in nanoseconds.
+pub fn save_baseline(report: &Report, filename: &Path) -> Result<(), String> {
+    let mut contents = format!("day\t{}\n", STAGES.join("\t"));
+    for (day, timings) in report {
+        let nanos: Vec<_> = timings
+            .stages()
+            .iter()
+            .map(|time| time.as_nanos().to_string())
+            .collect();
+        contents.push_str(&format!("{}\t{}\n", day, nanos.join("\t")));
+    }
+    write(filename, contents).map_err(|err| err.to_string())
+}
+
+pub fn load_baseline(filename: &Path) -> Result<HashMap<u32, Timings>, String> {
+    let contents = read_to_string(filename).map_err(|err| err.to_string())?;
+    contents
+        .lines()
+        .zip(1..)
+        .skip(1)
+        .filter(|(line, _)| !line.trim().is_empty())
+        .map(|(line, line_num)| {
+            parse_baseline_row(line).ok_or_else(|| {
+                format!("Line {}: invalid baseline row", line_num)
+            })
+        })
+        .collect()
+}
+
+fn parse_baseline_row(line: &str) -> Option<(u32, Timings)> {
+    let fields = line
+        .split('\t')
+        .map(|field| field.trim().parse().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    match fields.as_slice() {
+        &[day, parse, part1, part2] => Some((
+            day as u32,
+            Timings {
+                parse: Duration::from_nanos(parse),
+                part1: Duration::from_nanos(part1),
+                part2: Duration::from_nanos(part2),
+            },
+        )),
+        _ => None,
+    }
+}
+
+/// Prints every stage that got slower than its baseline by more than the
+/// given percentage, returning whether any regression was found.
+pub fn compare(
+    report: &Report,
+    baseline: &HashMap<u32, Timings>,
+    threshold: f64,
+) -> bool {
+    let mut regressed = false;
+    for (day, timings) in report {
+        let base = match baseline.get(day) {
+            Some(base) => base,
+            None => continue,
+        };
+        for ((stage, time), base_time) in STAGES
+            .iter()
+            .zip(timings.stages().iter())
+            .zip(base.stages().iter())
+        {
+            let change = percent_change(*base_time, *time);
+            if change > threshold {
+                if !regressed {
+                    println!();
+                    println!("Regressions (more than {}% slower):", threshold);
+                    regressed = true;
+                }
+                println!(
+                    "Day {:02} {}: {} -> {} (+{:.1}%)",
+                    day,
+                    stage,
+                    format_duration(*base_time),
+                    format_duration(*time),
+                    change
+                );
+            }
+        }
+    }
+    if !regressed {
+        println!();
+        println!("No regressions against baseline");
+    }
+    regressed
+}
+
+fn percent_change(before: Duration, after: Duration) -> f64 {
+    let before = before.as_secs_f64();
+    if before == 0.0 {
+        return 0.0;
+    }
+    (after.as_secs_f64() - before) / before * 100.0
+}
@@ -0,0 +1,91 @@
+use common::runner::{self, Options, PartResult};
+use common::{Error, ParseError, Solution};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+pub type Solver = fn(&str, &Options) -> Result<Vec<PartResult>, ParseError>;
+pub type Bencher = fn(&str, &[u32], usize) -> Result<Timings, Error>;
+
+pub struct Day {
+    pub solve: Solver,
+    pub bench: Bencher,
+}
+
+/// Best time out of all runs of each stage of a day's solution
+#[derive(Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+/// Solutions for every day, in order
+pub const DAYS: [Day; 25] = [
+    day::<day01::Day01>(),
+    day::<day02::Day02>(),
+    day::<day03::Day03>(),
+    day::<day04::Day04>(),
+    day::<day05::Day05>(),
+    day::<day06::Day06>(),
+    day::<day07::Day07>(),
+    day::<day08::Day08>(),
+    day::<day09::Day09>(),
+    day::<day10::Day10>(),
+    day::<day11::Day11>(),
+    day::<day12::Day12>(),
+    day::<day13::Day13>(),
+    day::<day14::Day14>(),
+    day::<day15::Day15>(),
+    day::<day16::Day16>(),
+    day::<day17::Day17>(),
+    day::<day18::Day18>(),
+    day::<day19::Day19>(),
+    day::<day20::Day20>(),
+    day::<day21::Day21>(),
+    day::<day22::Day22>(),
+    day::<day23::Day23>(),
+    day::<day24::Day24>(),
+    day::<day25::Day25>(),
+];
+
+/// Times parsing and the given parts of the puzzle, leaving the others at
+/// zero.
+fn bench<S: Solution>(
+    input: &str,
+    parts: &[u32],
+    runs: usize,
+) -> Result<Timings, Error> {
+    let parsed = S::parse(input)?;
+    let timed = |part| parts.contains(&part) && part <= S::PARTS;
+    Ok(Timings {
+        parse: best_time(runs, || S::parse(input)),
+        part1: if timed(1) {
+            best_time(runs, || S::part1(&parsed))
+        } else {
+            Duration::ZERO
+        },
+        part2: if timed(2) {
+            best_time(runs, || S::part2(&parsed))
+        } else {
+            Duration::ZERO
+        },
+    })
+}
+
+fn best_time<T, F: FnMut() -> T>(runs: usize, mut run: F) -> Duration {
+    (0..runs.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(run());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+const fn day<S: Solution>() -> Day {
+    Day {
+        solve: runner::solve::<S>,
+        bench: bench::<S>,
+    }
+}
@@ -0,0 +1,273 @@
+use clap::{crate_description, App, AppSettings, Arg, ArgMatches, SubCommand};
+use common::runner::{self, DayResult, Options};
+use common::samples::load_samples;
+use days::{Day, Timings, DAYS};
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+mod bench;
+mod days;
+
+fn main() {
+    let args = App::new(crate_description!())
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            runner::shared_args(SubCommand::with_name("run"))
+                .about("Runs the solution for one or all days")
+                .arg(
+                    Arg::with_name("DAY")
+                        .help("Day to run (1-25) or 'all'")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("INPUT")
+                        .help(
+                            "Files with puzzle input, or '-' for standard \
+                             input (single day only)",
+                        )
+                        .multiple(true)
+                        .index(2),
+                )
+                .arg(
+                    Arg::with_name("inputs")
+                        .long("inputs")
+                        .value_name("DIR")
+                        .default_value(".")
+                        .help("Directory with a dayNN/input file per day"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("bench")
+                .about("Times parsing and both parts of the samples for one or all days")
+                .arg(
+                    Arg::with_name("DAY")
+                        .help("Day to benchmark (1-25) or 'all'")
+                        .default_value("all")
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("samples")
+                        .long("samples")
+                        .value_name("DIR")
+                        .help(
+                            "Directory with a dayNN/samples directory per \
+                             day, the current one by default",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("inputs")
+                        .long("inputs")
+                        .value_name("DIR")
+                        .conflicts_with("samples")
+                        .help(
+                            "Times the dayNN/input file of each day in this \
+                             directory instead of the samples",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("runs")
+                        .long("runs")
+                        .value_name("N")
+                        .default_value("5")
+                        .help("Number of runs of each stage, best one is kept"),
+                )
+                .arg(
+                    Arg::with_name("save")
+                        .long("save")
+                        .value_name("FILE")
+                        .help("Saves timings as a baseline (TSV)"),
+                )
+                .arg(
+                    Arg::with_name("baseline")
+                        .long("baseline")
+                        .value_name("FILE")
+                        .help("Compares timings against a saved baseline"),
+                )
+                .arg(
+                    Arg::with_name("threshold")
+                        .long("threshold")
+                        .value_name("PERCENT")
+                        .default_value("10")
+                        .help("Slowdown over baseline flagged as regression"),
+                ),
+        )
+        .get_matches();
+
+    match args.subcommand() {
+        ("run", Some(run_args)) => run(run_args),
+        ("bench", Some(bench_args)) => bench(bench_args),
+        _ => unreachable!(),
+    }
+}
+
+fn run(run_args: &ArgMatches) {
+    let inputs_dir = Path::new(run_args.value_of("inputs").unwrap());
+    let days = match select_days(run_args.value_of("DAY").unwrap()) {
+        Ok(days) => days,
+        Err(err) => {
+            println!("{}", err);
+            exit(2);
+        }
+    };
+
+    let options = runner::options_arg(run_args);
+    let results: Vec<_> = match run_args.values_of("INPUT") {
+        Some(filenames) => {
+            if days.len() > 1 {
+                println!("Input files can only be given for a single day");
+                exit(2);
+            }
+            if let Err(err) = runner::check_inputs(filenames.clone()) {
+                println!("{}", err);
+                exit(2);
+            }
+            let (day, solution) = days[0];
+            filenames
+                .map(|filename| solve_day(day, solution, filename, &options))
+                .collect()
+        }
+        None => days
+            .iter()
+            .map(|&(day, solution)| {
+                let filename = default_input(inputs_dir, day);
+                solve_day(day, solution, &filename.to_string_lossy(), &options)
+            })
+            .collect(),
+    };
+    runner::report(&results, run_args);
+}
+
+fn bench(bench_args: &ArgMatches) {
+    let days = select_days(bench_args.value_of("DAY").unwrap())
+        .unwrap_or_else(|err| fail(&err));
+    let runs = bench_args
+        .value_of("runs")
+        .unwrap()
+        .parse()
+        .unwrap_or_else(|_| fail("Invalid number of runs"));
+    let threshold = bench_args
+        .value_of("threshold")
+        .unwrap()
+        .parse()
+        .unwrap_or_else(|_| fail("Invalid regression threshold"));
+    let baseline = bench_args.value_of("baseline").map(|filename| {
+        bench::load_baseline(Path::new(filename)).unwrap_or_else(|err| {
+            fail(&format!("Failed to read baseline: {}", err))
+        })
+    });
+
+    let mut report = Vec::new();
+    let mut skipped = Vec::new();
+    for (day, solution) in days {
+        let timings = match bench_args.value_of("inputs") {
+            Some(inputs_dir) => {
+                let filename = default_input(Path::new(inputs_dir), day);
+                bench_file(solution, &filename, &[1, 2], runs)
+            }
+            None => {
+                let root =
+                    Path::new(bench_args.value_of("samples").unwrap_or("."));
+                let dir = samples_dir(root, day);
+                if !dir.is_dir() {
+                    skipped.push(day);
+                    continue;
+                }
+                bench_samples(solution, &dir, runs)
+            }
+        };
+        let timings = timings.unwrap_or_else(|err| {
+            fail(&format!("Failed to benchmark day {:02}: {}", day, err))
+        });
+        report.push((day, timings));
+    }
+
+    bench::print_table(&report);
+    if !skipped.is_empty() {
+        let days: Vec<_> =
+            skipped.iter().map(|day| format!("{:02}", day)).collect();
+        println!("Skipped days without samples: {}", days.join(", "));
+    }
+    if let Some(filename) = bench_args.value_of("save") {
+        bench::save_baseline(&report, Path::new(filename)).unwrap_or_else(
+            |err| fail(&format!("Failed to save baseline: {}", err)),
+        );
+    }
+    if let Some(baseline) = baseline {
+        if bench::compare(&report, &baseline, threshold) {
+            exit(1);
+        }
+    }
+}
+
+fn fail(message: &str) -> ! {
+    println!("{}", message);
+    exit(2);
+}
+
+fn select_days(day: &str) -> Result<Vec<(u32, &'static Day)>, String> {
+    if day == "all" {
+        return Ok((1..).zip(DAYS.iter()).collect());
+    }
+    day.parse::<u32>()
+        .ok()
+        .filter(|number| (1..=25).contains(number))
+        .map(|number| vec![(number, &DAYS[number as usize - 1])])
+        .ok_or_else(|| format!("Invalid day '{}'", day))
+}
+
+fn default_input(inputs_dir: &Path, day: u32) -> PathBuf {
+    inputs_dir.join(format!("day{:02}", day)).join("input")
+}
+
+fn samples_dir(root: &Path, day: u32) -> PathBuf {
+    root.join(format!("day{:02}", day)).join("samples")
+}
+
+/// Best timings of the given parts of a day on one input file.
+fn bench_file(
+    solution: &Day,
+    filename: &Path,
+    parts: &[u32],
+    runs: usize,
+) -> Result<Timings, String> {
+    let input = read_to_string(filename)
+        .map_err(|err| format!("{}: {}", filename.display(), err))?;
+    (solution.bench)(&input, parts, runs)
+        .map_err(|err| format!("{}: {}", filename.display(), err))
+}
+
+/// Total of the best timings on every sample of a day, only timing the
+/// parts each sample has an answer for.
+fn bench_samples(
+    solution: &Day,
+    dir: &Path,
+    runs: usize,
+) -> Result<Timings, String> {
+    let mut total = Timings::default();
+    for sample in load_samples(dir)? {
+        let parts: Vec<_> = [(1, &sample.part1), (2, &sample.part2)]
+            .iter()
+            .filter(|(_, answer)| answer.is_some())
+            .map(|&(part, _)| part)
+            .collect();
+        total += bench_file(solution, &dir.join(&sample.input), &parts, runs)?;
+    }
+    Ok(total)
+}
+
+fn solve_day(
+    day: u32,
+    solution: &Day,
+    filename: &str,
+    options: &Options,
+) -> DayResult {
+    let parts =
+        runner::parse_file(filename, |input| (solution.solve)(input, options));
+    DayResult {
+        day,
+        input: filename.to_string(),
+        parts,
+    }
+}
@@ -0,0 +1,751 @@
+use crate::{ErrorKind, ParseError};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// How the cells of an automaton are connected to each other.
+pub trait Topology {
+    type Cell: Clone + Eq + Hash;
+
+    /// Cells adjacent to the given one.
+    fn neighbours(&self, cell: &Self::Cell) -> Vec<Self::Cell>;
+
+    /// Every cell, if there's a finite number of them. Only finite
+    /// topologies support rules where cells are born with no neighbours.
+    fn cells(&self) -> Option<Vec<Self::Cell>> {
+        None
+    }
+
+    /// Largest number of neighbours of any cell, if it's bounded.
+    fn max_neighbours(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// An infinite grid of cubes in N dimensions, where every cube is adjacent
+/// to the 3^N - 1 cubes that differ by at most one in every coordinate.
+pub struct Cubic<const N: usize> {
+    deltas: Vec<[i32; N]>,
+}
+
+impl<const N: usize> Cubic<N> {
+    pub fn new() -> Self {
+        let mut deltas = vec![[0; N]];
+        for dim in 0..N {
+            deltas = deltas
+                .into_iter()
+                .flat_map(|delta| {
+                    (-1..=1).map(move |shift| {
+                        let mut delta = delta;
+                        delta[dim] = shift;
+                        delta
+                    })
+                })
+                .collect();
+        }
+        deltas.retain(|delta| delta.iter().any(|&shift| shift != 0));
+        Self { deltas }
+    }
+}
+
+impl<const N: usize> Default for Cubic<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Topology for Cubic<N> {
+    type Cell = [i32; N];
+
+    fn neighbours(&self, cell: &Self::Cell) -> Vec<Self::Cell> {
+        self.deltas
+            .iter()
+            .map(|delta| {
+                let mut neighbour = *cell;
+                for (coord, shift) in neighbour.iter_mut().zip(delta.iter()) {
+                    *coord += shift;
+                }
+                neighbour
+            })
+            .collect()
+    }
+
+    fn max_neighbours(&self) -> Option<usize> {
+        Some(self.deltas.len())
+    }
+}
+
+/// An infinite grid of hexagons in axial coordinates `(q, r)`, where `q`
+/// grows to the east and `r` to the south-east.
+#[derive(Default)]
+pub struct Hexagonal;
+
+/// Direction to each adjacent hexagon: east, south-east, south-west, west,
+/// north-west and north-east
+pub const HEX_DIRECTIONS: [(i32, i32); 6] =
+    [(1, 0), (0, 1), (-1, 1), (-1, 0), (0, -1), (1, -1)];
+
+impl Topology for Hexagonal {
+    type Cell = (i32, i32);
+
+    fn neighbours(&self, &(q, r): &Self::Cell) -> Vec<Self::Cell> {
+        HEX_DIRECTIONS
+            .iter()
+            .map(|(delta_q, delta_r)| (q + delta_q, r + delta_r))
+            .collect()
+    }
+
+    fn max_neighbours(&self) -> Option<usize> {
+        Some(HEX_DIRECTIONS.len())
+    }
+}
+
+/// A finite set of cells with precomputed neighbours, for automata whose
+/// adjacency doesn't follow a regular pattern.
+pub struct Graph<C> {
+    neighbours: HashMap<C, Vec<C>>,
+}
+
+impl<C: Clone + Eq + Hash> Graph<C> {
+    /// Builds the graph from the neighbours of every cell.
+    pub fn new(neighbours: HashMap<C, Vec<C>>) -> Self {
+        Self { neighbours }
+    }
+}
+
+impl<C: Clone + Eq + Hash> Topology for Graph<C> {
+    type Cell = C;
+
+    fn neighbours(&self, cell: &Self::Cell) -> Vec<Self::Cell> {
+        self.neighbours.get(cell).cloned().unwrap_or_default()
+    }
+
+    fn cells(&self) -> Option<Vec<Self::Cell>> {
+        Some(self.neighbours.keys().cloned().collect())
+    }
+
+    fn max_neighbours(&self) -> Option<usize> {
+        Some(self.neighbours.values().map(Vec::len).max().unwrap_or(0))
+    }
+}
+
+/// Numbers of active neighbours for which an inactive cell becomes active
+/// (birth) and an active cell stays active (survival).
+///
+/// Rules are written as `B` followed by the birth counts, then `/S` followed
+/// by the survival counts, with a digit per count, e.g. `B3/S23` for Conway's
+/// Game of Life. Counts above 9 are written as a list of counts and
+/// inclusive ranges separated by commas instead, e.g. `B3,12/S2..13`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rule {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+
+impl Rule {
+    pub fn new(birth: &[usize], survival: &[usize]) -> Self {
+        let sorted = |counts: &[usize]| {
+            let mut counts = counts.to_vec();
+            counts.sort_unstable();
+            counts.dedup();
+            counts
+        };
+        Self {
+            birth: sorted(birth),
+            survival: sorted(survival),
+        }
+    }
+
+    /// Whether a cell is active in the next generation.
+    pub fn next_state(&self, active: bool, active_neighbours: usize) -> bool {
+        if active {
+            self.survival.contains(&active_neighbours)
+        } else {
+            self.birth.contains(&active_neighbours)
+        }
+    }
+
+    /// Checks that cells of the topology can have every count of the rule.
+    pub fn check<T: Topology>(&self, topology: &T) -> Result<(), String> {
+        let largest = self.birth.iter().chain(&self.survival).max();
+        match (largest, topology.max_neighbours()) {
+            (Some(&count), Some(neighbours)) if count > neighbours => {
+                Err(format!(
+                    "rule {} counts {} neighbours, but cells have at most {}",
+                    self, count, neighbours
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Largest neighbour count in a rule, well above the number of neighbours
+/// of any topology here, which keeps a range from taking up all memory
+const MAX_COUNT: usize = 1 << 16;
+
+/// Parses the counts in a rule, which start at the given column: either a
+/// digit per count, or counts and ranges separated by commas.
+fn parse_counts(s: &str, column: usize) -> Result<Vec<usize>, ParseError> {
+    if !s.contains(',') && !s.contains("..") {
+        return s
+            .chars()
+            .zip(column..)
+            .map(|(ch, column)| {
+                ch.to_digit(10).map(|count| count as usize).ok_or_else(|| {
+                    ParseError::new(ErrorKind::InvalidNumber("neighbour count"))
+                        .with_token(ch)
+                        .with_column(column)
+                })
+            })
+            .collect();
+    }
+
+    let mut counts = Vec::new();
+    let mut column = column;
+    for item in s.split(',') {
+        let invalid =
+            |kind| ParseError::new(kind).with_token(item).with_column(column);
+        let (first, last) = item.split_once("..").unwrap_or((item, item));
+        let parse = |count: &str| {
+            count
+                .parse::<usize>()
+                .ok()
+                .filter(|&count| count <= MAX_COUNT)
+                .ok_or_else(|| {
+                    invalid(ErrorKind::InvalidNumber("neighbour count"))
+                })
+        };
+        let (first, last) = (parse(first)?, parse(last)?);
+        if first > last {
+            return Err(invalid(ErrorKind::InvalidValue(
+                "empty range of neighbour counts",
+            )));
+        }
+        counts.extend(first..=last);
+        column += item.chars().count() + 1;
+    }
+    Ok(counts)
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rest = s.strip_prefix('B').ok_or_else(|| {
+            ParseError::new(ErrorKind::Expected("rule starting with 'B'"))
+                .with_token(s)
+        })?;
+        let (birth, survival) = rest.split_once("/S").ok_or_else(|| {
+            ParseError::new(ErrorKind::Missing("'/S' in rule"))
+        })?;
+        let survival_column = birth.chars().count() + 4;
+        let birth = parse_counts(birth, 2)?;
+        let survival = parse_counts(survival, survival_column)?;
+        Ok(Self::new(&birth, &survival))
+    }
+}
+
+/// Writes counts as digits when they all have one, and as a list of counts
+/// and ranges otherwise.
+fn write_counts(f: &mut Formatter<'_>, counts: &[usize]) -> std::fmt::Result {
+    if counts.iter().all(|&count| count < 10) {
+        for count in counts {
+            write!(f, "{}", count)?;
+        }
+        return Ok(());
+    }
+    // Counts are sorted, so each run of consecutive ones is a range
+    let mut start = 0;
+    while start < counts.len() {
+        let mut end = start + 1;
+        while end < counts.len() && counts[end] == counts[end - 1] + 1 {
+            end += 1;
+        }
+        if start > 0 {
+            write!(f, ",")?;
+        }
+        match end - start {
+            1 => write!(f, "{}", counts[start])?,
+            2 => write!(f, "{},{}", counts[start], counts[start + 1])?,
+            _ => write!(f, "{}..{}", counts[start], counts[end - 1])?,
+        }
+        start = end;
+    }
+    Ok(())
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "B")?;
+        write_counts(f, &self.birth)?;
+        write!(f, "/S")?;
+        write_counts(f, &self.survival)
+    }
+}
+
+/// A cellular automaton where every cell is either active or inactive, and
+/// all cells are updated at once in each generation according to a rule.
+pub struct Automaton<T: Topology> {
+    topology: T,
+    rule: Rule,
+    active: HashSet<T::Cell>,
+    generation: usize,
+}
+
+impl<T: Topology> Automaton<T> {
+    /// Creates an automaton with the given cells active.
+    ///
+    /// Panics if the rule lets cells with no active neighbours be born in a
+    /// topology with infinite cells.
+    pub fn new<I>(topology: T, rule: Rule, active: I) -> Self
+    where
+        I: IntoIterator<Item = T::Cell>,
+    {
+        assert!(
+            topology.cells().is_some() || !rule.next_state(false, 0),
+            "Infinite topologies can't have cells born with no neighbours"
+        );
+        Self {
+            topology,
+            rule,
+            active: active.into_iter().collect(),
+            generation: 0,
+        }
+    }
+
+    pub fn active(&self) -> &HashSet<T::Cell> {
+        &self.active
+    }
+
+    pub fn count_active(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Number of generations run so far
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Advances one generation, returning whether any cell changed.
+    pub fn step(&mut self) -> bool {
+        let mut active_neighbours: HashMap<T::Cell, usize> = HashMap::new();
+        for cell in self.active.iter() {
+            for neighbour in self.topology.neighbours(cell) {
+                *active_neighbours.entry(neighbour).or_insert(0) += 1;
+            }
+        }
+
+        let rule = &self.rule;
+        let active = &self.active;
+        let next_state = |cell: &T::Cell| {
+            let count = active_neighbours.get(cell).copied().unwrap_or(0);
+            rule.next_state(active.contains(cell), count)
+        };
+        let next: HashSet<T::Cell> = match self.topology.cells() {
+            Some(cells) => cells.into_iter().filter(next_state).collect(),
+            // Only active cells and their neighbours can be active next
+            None => active_neighbours
+                .keys()
+                .chain(active.iter())
+                .filter(|cell| next_state(cell))
+                .cloned()
+                .collect(),
+        };
+
+        let changed = next != self.active;
+        self.active = next;
+        self.generation += 1;
+        changed
+    }
+
+    /// Advances the given number of generations.
+    pub fn run(&mut self, cycles: usize) {
+        for _ in 0..cycles {
+            self.step();
+        }
+    }
+
+    /// Advances until a generation leaves every cell unchanged, returning
+    /// the number of generations that changed something. Never returns if
+    /// the automaton doesn't settle, e.g. if it oscillates.
+    pub fn run_until_stable(&mut self) -> usize {
+        let mut changes = 0;
+        while self.step() {
+            changes += 1;
+        }
+        changes
+    }
+}
+
+/// One bit per cell of a dense automaton, set when the cell is active
+#[derive(Clone, Eq, PartialEq)]
+struct Bits {
+    words: Vec<u64>,
+}
+
+impl Bits {
+    fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    fn get(&self, index: usize) -> bool {
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    fn set(&mut self, index: usize, value: bool) {
+        if value {
+            self.words[index / 64] |= 1 << (index % 64);
+        } else {
+            self.words[index / 64] &= !(1 << (index % 64));
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+}
+
+/// Cells related to each cell, as a list per cell stored back to back
+struct Links {
+    /// The cells linked to cell `i` are `cells[offsets[i]..offsets[i + 1]]`
+    offsets: Vec<usize>,
+    cells: Vec<usize>,
+}
+
+impl Links {
+    fn from_lists(lists: &[Vec<usize>]) -> Self {
+        let mut offsets = Vec::with_capacity(lists.len() + 1);
+        offsets.push(0);
+        let mut cells = Vec::new();
+        for list in lists {
+            cells.extend_from_slice(list);
+            offsets.push(cells.len());
+        }
+        Self { offsets, cells }
+    }
+
+    fn of(&self, cell: usize) -> &[usize] {
+        &self.cells[self.offsets[cell]..self.offsets[cell + 1]]
+    }
+}
+
+/// What never changes about a dense automaton, shared by all its states
+struct DenseLayout<C> {
+    cells: Vec<C>,
+    indices: HashMap<C, usize>,
+    /// The cells whose activity each cell counts
+    counted: Links,
+    /// The reverse of `counted`: the cells that count each cell
+    counting: Links,
+    rule: Rule,
+}
+
+/// A cellular automaton on a finite topology, with the cells numbered and
+/// their states stored in flat arrays. Each generation only looks at the
+/// cells that changed in the previous one and the cells counting them, so
+/// large automata that settle down get cheaper as they go.
+///
+/// Cloning is cheap, as only the states of the cells are copied.
+#[derive(Clone)]
+pub struct DenseAutomaton<C> {
+    layout: Rc<DenseLayout<C>>,
+    active: Bits,
+    /// Cells that may change in the next generation, as nothing they count
+    /// changed otherwise
+    pending: Vec<usize>,
+    generation: usize,
+}
+
+impl<C: Clone + Eq + Hash> DenseAutomaton<C> {
+    /// Creates an automaton with the given cells active, ignoring cells and
+    /// neighbours that aren't part of the topology.
+    ///
+    /// Panics if the topology has infinite cells.
+    pub fn new<T, I>(topology: &T, rule: Rule, active: I) -> Self
+    where
+        T: Topology<Cell = C>,
+        I: IntoIterator<Item = C>,
+    {
+        let cells = topology
+            .cells()
+            .expect("Dense automata need a finite topology");
+        let indices: HashMap<_, _> = cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (cell.clone(), index))
+            .collect();
+        let mut counted = vec![Vec::new(); cells.len()];
+        let mut counting = vec![Vec::new(); cells.len()];
+        for (index, cell) in cells.iter().enumerate() {
+            for neighbour in topology.neighbours(cell) {
+                if let Some(&neighbour) = indices.get(&neighbour) {
+                    counted[neighbour].push(index);
+                    counting[index].push(neighbour);
+                }
+            }
+        }
+
+        let mut states = Bits::new(cells.len());
+        for cell in active {
+            if let Some(&index) = indices.get(&cell) {
+                states.set(index, true);
+            }
+        }
+        let pending = (0..cells.len()).collect();
+        let layout = DenseLayout {
+            cells,
+            indices,
+            counted: Links::from_lists(&counted),
+            counting: Links::from_lists(&counting),
+            rule,
+        };
+        Self {
+            layout: Rc::new(layout),
+            active: states,
+            pending,
+            generation: 0,
+        }
+    }
+
+    /// Every cell of the topology, along with whether it's active.
+    pub fn states(&self) -> impl Iterator<Item = (&C, bool)> + '_ {
+        self.layout
+            .cells
+            .iter()
+            .enumerate()
+            .map(move |(index, cell)| (cell, self.active.get(index)))
+    }
+
+    pub fn is_active(&self, cell: &C) -> bool {
+        self.layout
+            .indices
+            .get(cell)
+            .is_some_and(|&index| self.active.get(index))
+    }
+
+    pub fn count_active(&self) -> usize {
+        self.active.count()
+    }
+
+    /// Number of generations run so far
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    fn next_state(&self, cell: usize) -> bool {
+        let active_neighbours = self
+            .layout
+            .counted
+            .of(cell)
+            .iter()
+            .filter(|&&other| self.active.get(other))
+            .count();
+        self.layout
+            .rule
+            .next_state(self.active.get(cell), active_neighbours)
+    }
+
+    /// Advances one generation, returning the number of cells that changed.
+    pub fn step(&mut self) -> usize {
+        let changed: Vec<_> = self
+            .pending
+            .iter()
+            .copied()
+            .filter(|&cell| self.next_state(cell) != self.active.get(cell))
+            .collect();
+
+        let mut queued = Bits::new(self.layout.cells.len());
+        self.pending.clear();
+        for &cell in changed.iter() {
+            self.active.set(cell, !self.active.get(cell));
+            for &other in
+                std::iter::once(&cell).chain(self.layout.counting.of(cell))
+            {
+                if !queued.get(other) {
+                    queued.set(other, true);
+                    self.pending.push(other);
+                }
+            }
+        }
+        self.generation += 1;
+        changed.len()
+    }
+
+    /// Advances the given number of generations.
+    pub fn run(&mut self, cycles: usize) {
+        for _ in 0..cycles {
+            self.step();
+        }
+    }
+
+    /// Advances until a generation leaves every cell unchanged, returning
+    /// the number of generations that changed something. Never returns if
+    /// the automaton doesn't settle, e.g. if it oscillates.
+    pub fn run_until_stable(&mut self) -> usize {
+        let mut changes = 0;
+        while self.step() > 0 {
+            changes += 1;
+        }
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life() -> Rule {
+        Rule::new(&[3], &[2, 3])
+    }
+
+    #[test]
+    fn parse_rule() {
+        let rule: Rule = "B3/S23".parse().unwrap();
+        assert_eq!(rule, life());
+        assert_eq!(rule.to_string(), "B3/S23");
+        assert_eq!("B/S".parse::<Rule>().unwrap().to_string(), "B/S");
+        assert_eq!("B21/S112".parse::<Rule>().unwrap().to_string(), "B12/S12");
+    }
+
+    #[test]
+    fn invalid_rule() {
+        assert!("S23/B3".parse::<Rule>().is_err());
+        assert!("B3".parse::<Rule>().is_err());
+        let err = "B3/S2x".parse::<Rule>().unwrap_err();
+        assert_eq!(err.token(), Some("x"));
+        assert_eq!(err.column(), Some(6));
+        let err = "B3,1x/S2".parse::<Rule>().unwrap_err();
+        assert_eq!((err.token(), err.column()), (Some("1x"), Some(4)));
+        let err = "B3/S2,,3".parse::<Rule>().unwrap_err();
+        assert_eq!((err.token(), err.column()), (Some(""), Some(7)));
+        let err = "B33/S2..x".parse::<Rule>().unwrap_err();
+        assert_eq!((err.token(), err.column()), (Some("2..x"), Some(6)));
+        assert!("B5..3/S".parse::<Rule>().is_err());
+        assert!("B3/S0..100000000000".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn multi_digit_counts() {
+        let rule: Rule = "B3,12/S2..13".parse().unwrap();
+        assert_eq!(rule, Rule::new(&[3, 12], &(2..=13).collect::<Vec<_>>()));
+        assert_eq!(rule.to_string(), "B3,12/S2..13");
+        assert!(rule.next_state(false, 12));
+        assert!(!rule.next_state(false, 1));
+        // Single digit counts are still written as digits
+        assert_eq!(
+            "B3,6/S2..3".parse::<Rule>().unwrap().to_string(),
+            "B36/S23"
+        );
+        let rule: Rule = "B10,11,12,14,20,21/S".parse().unwrap();
+        assert_eq!(rule.to_string(), "B10..12,14,20,21/S");
+    }
+
+    #[test]
+    fn check_counts() {
+        let rule: Rule = "B3,12/S2,3".parse().unwrap();
+        assert!(rule.check(&Cubic::<3>::new()).is_ok());
+        assert_eq!(
+            rule.check(&Cubic::<2>::new()),
+            Err(
+                "rule B3,12/S23 counts 12 neighbours, but cells have at most 8"
+                    .to_string()
+            )
+        );
+        assert!(life().check(&Hexagonal).is_ok());
+        assert!("B7/S".parse::<Rule>().unwrap().check(&Hexagonal).is_err());
+    }
+
+    #[test]
+    fn cubic_neighbours() {
+        assert_eq!(Cubic::<2>::new().neighbours(&[0, 0]).len(), 8);
+        assert_eq!(Cubic::<3>::new().neighbours(&[0, 0, 0]).len(), 26);
+        assert_eq!(Cubic::<4>::new().neighbours(&[0, 0, 0, 0]).len(), 80);
+    }
+
+    #[test]
+    fn blinker_oscillates() {
+        let horizontal = vec![[-1, 0], [0, 0], [1, 0]];
+        let vertical: HashSet<_> =
+            vec![[0, -1], [0, 0], [0, 1]].into_iter().collect();
+        let mut automaton =
+            Automaton::new(Cubic::<2>::new(), life(), horizontal);
+        assert!(automaton.step());
+        assert_eq!(automaton.active(), &vertical);
+        automaton.run(2);
+        assert_eq!(automaton.generation(), 3);
+        assert_eq!(automaton.active(), &vertical);
+    }
+
+    #[test]
+    fn block_is_stable() {
+        let block = vec![[0, 0], [0, 1], [1, 0], [1, 1]];
+        let mut automaton = Automaton::new(Cubic::<2>::new(), life(), block);
+        assert_eq!(automaton.run_until_stable(), 0);
+        assert_eq!(automaton.count_active(), 4);
+    }
+
+    #[test]
+    fn graph_with_birth_from_nothing() {
+        // A line of 3 cells, where cells are born with no active neighbours
+        // and die with any
+        let neighbours = vec![(0, vec![1]), (1, vec![0, 2]), (2, vec![1])];
+        let graph = Graph::new(neighbours.into_iter().collect());
+        let mut automaton = Automaton::new(graph, Rule::new(&[0], &[0]), None);
+        assert!(automaton.step());
+        assert_eq!(automaton.count_active(), 3);
+        assert!(automaton.step());
+        assert_eq!(automaton.count_active(), 0);
+    }
+
+    #[test]
+    fn dense_matches_sparse() {
+        // A glider on a 6x6 torus, which the sparse automaton can't wrap
+        // around, so compare them before it reaches the edge
+        let cells: Vec<_> =
+            (0..6).flat_map(|x| (0..6).map(move |y| [x, y])).collect();
+        let cubic = Cubic::<2>::new();
+        let neighbours = cells
+            .iter()
+            .map(|cell| {
+                let wrapped = cubic
+                    .neighbours(cell)
+                    .into_iter()
+                    .map(|[x, y]| [(x + 6) % 6, (y + 6) % 6])
+                    .collect();
+                (*cell, wrapped)
+            })
+            .collect();
+        let glider = vec![[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]];
+        let mut dense = DenseAutomaton::new(
+            &Graph::new(neighbours),
+            life(),
+            glider.clone(),
+        );
+        let mut sparse = Automaton::new(cubic, life(), glider);
+        for _ in 0..2 {
+            assert_eq!(dense.step(), 4);
+            sparse.step();
+            assert_eq!(dense.count_active(), sparse.count_active());
+            assert!(sparse.active().iter().all(|cell| dense.is_active(cell)));
+        }
+        // After 24 generations the glider is back where it started
+        dense.run(22);
+        assert_eq!(dense.generation(), 24);
+        assert!([[1, 0], [2, 1], [0, 2]].iter().all(|c| dense.is_active(c)));
+        assert_eq!(dense.count_active(), 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn infinite_birth_from_nothing() {
+        Automaton::new(Hexagonal, Rule::new(&[0], &[]), None);
+    }
+}
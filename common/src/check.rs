@@ -0,0 +1,118 @@
+use crate::runner::DayResult;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::read_to_string;
+
+/// An answer as written in the answers file, which can be a number or text
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl Answer {
+    fn into_string(self) -> String {
+        match self {
+            Self::Number(number) => number.to_string(),
+            Self::Text(text) => text,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct DayAnswers {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+/// Expected answers for each part of each day
+pub struct Expected {
+    answers: HashMap<(u32, u32), String>,
+}
+
+impl Expected {
+    /// Reads a TOML file with a `dayNN` table per day, each with optional
+    /// `part1` and `part2` answers.
+    pub fn load(filename: &str) -> Result<Self, String> {
+        let contents =
+            read_to_string(filename).map_err(|err| err.to_string())?;
+        let days: HashMap<String, DayAnswers> =
+            toml::from_str(&contents).map_err(|err| err.to_string())?;
+
+        let mut answers = HashMap::new();
+        for (key, day_answers) in days {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .filter(|day| (1..=25).contains(day))
+                .ok_or_else(|| format!("Invalid day '{}'", key))?;
+            let parts = [(1, day_answers.part1), (2, day_answers.part2)];
+            for (part, answer) in parts {
+                if let Some(answer) = answer {
+                    answers.insert((day, part), answer.into_string());
+                }
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+}
+
+/// Compares every answer with the expected one, printing PASS or FAIL for
+/// each part. Returns whether all answers matched, which needs at least one
+/// answer to compare.
+pub fn check_results(results: &[DayResult], expected: &Expected) -> bool {
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut skipped = 0;
+
+    for result in results {
+        let label = format!("Day {:02} ({})", result.day, result.input);
+        let parts = match &result.parts {
+            Ok(parts) => parts,
+            Err(err) => {
+                println!("FAIL {}: {}", label, err);
+                failed += 1;
+                continue;
+            }
+        };
+        for part in parts {
+            let expected = match expected.get(result.day, part.part) {
+                Some(expected) => expected,
+                None => {
+                    println!(
+                        "SKIP {} part {}: no expected answer",
+                        label, part.part
+                    );
+                    skipped += 1;
+                    continue;
+                }
+            };
+            let actual = match &part.answer {
+                Ok(answer) => answer.clone(),
+                Err(err) => err.to_string(),
+            };
+            if part.answer.is_ok() && actual == expected {
+                println!("PASS {} part {}", label, part.part);
+                passed += 1;
+            } else {
+                println!("FAIL {} part {}", label, part.part);
+                println!("  expected: {}", expected);
+                println!("  actual:   {}", actual);
+                failed += 1;
+            }
+        }
+    }
+
+    println!();
+    println!("{} passed, {} failed, {} skipped", passed, failed, skipped);
+    if passed + failed == 0 {
+        println!("No answer was checked, as none is expected");
+        return false;
+    }
+    failed == 0
+}
@@ -0,0 +1,155 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+pub enum Error {
+    /// The puzzle input is malformed
+    Parse(ParseError),
+    /// The puzzle has no answer for the given input
+    NotFound,
+    /// The puzzle could not be solved for the given input
+    Unsolvable(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse(err) => write!(f, "{}", err),
+            Self::NotFound => write!(f, "not found"),
+            Self::Unsolvable(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+/// What went wrong while parsing, with a short description of the item
+/// being parsed (e.g. "bus number" or "password rule").
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// Text that does not follow the expected syntax
+    InvalidSyntax(&'static str),
+    /// A token that is not a valid number
+    InvalidNumber(&'static str),
+    /// A character that is not allowed
+    InvalidCharacter(&'static str),
+    /// A well-formed value that is not acceptable
+    InvalidValue(&'static str),
+    /// A required part of the input is missing
+    Missing(&'static str),
+    /// Something other than the expected text was found
+    Expected(&'static str),
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSyntax(what) => write!(f, "invalid {}", what),
+            Self::InvalidNumber(what) => write!(f, "invalid {}", what),
+            Self::InvalidCharacter(what) => {
+                write!(f, "invalid character in {}", what)
+            }
+            Self::InvalidValue(reason) => write!(f, "{}", reason),
+            Self::Missing(what) => write!(f, "missing {}", what),
+            Self::Expected(what) => write!(f, "expected {}", what),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    kind: ErrorKind,
+    token: Option<String>,
+    line: Option<usize>,
+    column: Option<usize>,
+}
+
+impl ParseError {
+    pub fn new(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            token: None,
+            line: None,
+            column: None,
+        }
+    }
+
+    /// Records the offending token
+    pub fn with_token<S: ToString>(mut self, token: S) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// Records the column (starting at 1) of the offending token
+    pub fn with_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Places the error in a block of text that starts at the given line
+    /// (starting at 1). Errors already located at a line within the block
+    /// are shifted accordingly.
+    pub fn at_line(mut self, line_num: usize) -> Self {
+        self.line =
+            Some(self.line.map_or(line_num, |line| line + line_num - 1));
+        self
+    }
+
+    /// Places the error further along the line, for tokens that were parsed
+    /// from a part of the line starting at the given column.
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.column = Some(self.column.map_or(column, |col| col + column - 1));
+        self
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "Line {}, column {}: ", line, column)?
+            }
+            (Some(line), None) => write!(f, "Line {}: ", line)?,
+            (None, Some(column)) => write!(f, "Column {}: ", column)?,
+            (None, None) => (),
+        }
+        write!(f, "{}", self.kind)?;
+        match (self.kind, &self.token) {
+            (ErrorKind::Expected(_), Some(token)) => {
+                write!(f, " but found '{}'", token)
+            }
+            (_, Some(token)) => write!(f, " '{}'", token),
+            (_, None) => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
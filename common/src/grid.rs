@@ -0,0 +1,415 @@
+use crate::{ErrorKind, ParseError};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A position on a 2D map, with `x` growing to the right (columns) and `y`
+/// growing downwards (rows) from the top-left corner.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// The point reached by moving once in the given direction.
+    pub fn step(self, (delta_x, delta_y): (i32, i32)) -> Self {
+        Self::new(self.x + delta_x, self.y + delta_y)
+    }
+
+    /// All adjacent points, whether or not they are inside a grid.
+    pub fn neighbours(
+        self,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = Point> {
+        neighbourhood
+            .directions()
+            .iter()
+            .map(move |&direction| self.step(direction))
+    }
+}
+
+/// Which points count as adjacent
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Neighbourhood {
+    /// The 4 points up, down, left and right
+    Orthogonal,
+    /// The 8 points around, including diagonals
+    All,
+}
+
+const ORTHOGONAL: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+const ALL: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+impl Neighbourhood {
+    /// The direction to each adjacent point, as an `(x, y)` delta.
+    pub fn directions(self) -> &'static [(i32, i32)] {
+        match self {
+            Self::Orthogonal => &ORTHOGONAL,
+            Self::All => &ALL,
+        }
+    }
+}
+
+/// A type of grid cell, written as a single character in the puzzle input.
+/// The default cell is the background, e.g. `.` for empty space.
+pub trait Cell: Copy + Default + PartialEq {
+    fn from_char(ch: char) -> Option<Self>;
+    fn to_char(self) -> char;
+}
+
+/// `#` is on and `.` is off, as used by most puzzles
+impl Cell for bool {
+    fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        if self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+}
+
+/// A rectangular map of cells, with the operations shared by every storage.
+pub trait Grid<T: Cell> {
+    /// A grid with every cell set to the default one.
+    fn new(width: usize, height: usize) -> Self
+    where
+        Self: Sized;
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+
+    /// The cell at a point, which must be inside the grid.
+    fn cell(&self, point: Point) -> T;
+    /// Replaces the cell at a point, which must be inside the grid.
+    fn set(&mut self, point: Point, cell: T);
+
+    fn contains(&self, point: Point) -> bool {
+        point.x >= 0
+            && point.y >= 0
+            && (point.x as usize) < self.width()
+            && (point.y as usize) < self.height()
+    }
+
+    /// The cell at a point, or `None` if the point is outside the grid.
+    fn get(&self, point: Point) -> Option<T> {
+        if self.contains(point) {
+            Some(self.cell(point))
+        } else {
+            None
+        }
+    }
+
+    /// The point inside the grid that a point maps to when the grid repeats
+    /// in every direction.
+    fn wrap(&self, point: Point) -> Point {
+        Point::new(
+            point.x.rem_euclid(self.width() as i32),
+            point.y.rem_euclid(self.height() as i32),
+        )
+    }
+
+    /// The cell at a point, as if the grid repeated in every direction.
+    fn get_wrapping(&self, point: Point) -> T {
+        self.cell(self.wrap(point))
+    }
+
+    /// Every point in the grid, row by row.
+    fn points(&self) -> Box<dyn Iterator<Item = Point>> {
+        let width = self.width() as i32;
+        let height = self.height() as i32;
+        Box::new(
+            (0..height)
+                .flat_map(move |y| (0..width).map(move |x| Point::new(x, y))),
+        )
+    }
+
+    /// Points of every cell equal to the given one.
+    fn find_all(&self, cell: T) -> Vec<Point> {
+        self.points()
+            .filter(|&point| self.cell(point) == cell)
+            .collect()
+    }
+
+    fn count(&self, cell: T) -> usize {
+        self.points()
+            .filter(|&point| self.cell(point) == cell)
+            .count()
+    }
+
+    /// Adjacent points that are inside the grid.
+    fn neighbours(
+        &self,
+        point: Point,
+        neighbourhood: Neighbourhood,
+    ) -> Vec<Point> {
+        point
+            .neighbours(neighbourhood)
+            .filter(|&neighbour| self.contains(neighbour))
+            .collect()
+    }
+
+    /// The first point in the given direction whose cell can't be seen
+    /// through, or `None` if the edge of the grid is reached first.
+    fn line_of_sight<F>(
+        &self,
+        point: Point,
+        direction: (i32, i32),
+        see_through: F,
+    ) -> Option<Point>
+    where
+        F: Fn(T) -> bool,
+    {
+        let mut point = point.step(direction);
+        while let Some(cell) = self.get(point) {
+            if !see_through(cell) {
+                return Some(point);
+            }
+            point = point.step(direction);
+        }
+        None
+    }
+
+    /// Writes the grid back as text, one line per row.
+    fn render(&self) -> String {
+        let mut text =
+            String::with_capacity((self.width() + 1) * self.height());
+        for y in 0..self.height() as i32 {
+            for x in 0..self.width() as i32 {
+                text.push(self.cell(Point::new(x, y)).to_char());
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// Parses a grid of cells, one row per line. The grid is as wide as the
+/// longest line, and shorter lines are padded with the default cell. Invalid
+/// characters are reported as being in `what`, which is "grid" when parsing
+/// with `str::parse`.
+pub fn parse_grid<T: Cell, G: Grid<T>>(
+    s: &str,
+    what: &'static str,
+) -> Result<G, ParseError> {
+    let width = s
+        .lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut grid = G::new(width, s.lines().count());
+    for (line, y) in s.lines().zip(0..) {
+        for (ch, x) in line.chars().zip(0..) {
+            let cell = T::from_char(ch).ok_or_else(|| {
+                ParseError::new(ErrorKind::InvalidCharacter(what))
+                    .with_token(ch)
+                    .at_line(y as usize + 1)
+                    .with_column(x as usize + 1)
+            })?;
+            grid.set(Point::new(x, y), cell);
+        }
+    }
+    Ok(grid)
+}
+
+/// A grid that stores every cell, best for maps that are mostly filled.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DenseGrid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Cell> DenseGrid<T> {
+    fn index(&self, point: Point) -> usize {
+        point.y as usize * self.width + point.x as usize
+    }
+}
+
+impl<T: Cell> Grid<T> for DenseGrid<T> {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![T::default(); width * height],
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn cell(&self, point: Point) -> T {
+        self.cells[self.index(point)]
+    }
+
+    fn set(&mut self, point: Point, cell: T) {
+        let index = self.index(point);
+        self.cells[index] = cell;
+    }
+}
+
+impl<T: Cell> FromStr for DenseGrid<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_grid(s, "grid")
+    }
+}
+
+impl<T: Cell> Display for DenseGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+/// A grid that only stores cells other than the default one, best for maps
+/// that are mostly background.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SparseGrid<T> {
+    width: usize,
+    height: usize,
+    cells: HashMap<Point, T>,
+}
+
+impl<T: Cell> SparseGrid<T> {
+    /// Every cell other than the default one, in no particular order.
+    pub fn cells(&self) -> impl Iterator<Item = (Point, T)> + '_ {
+        self.cells.iter().map(|(&point, &cell)| (point, cell))
+    }
+}
+
+impl<T: Cell> Grid<T> for SparseGrid<T> {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn cell(&self, point: Point) -> T {
+        self.cells.get(&point).copied().unwrap_or_default()
+    }
+
+    fn set(&mut self, point: Point, cell: T) {
+        if cell == T::default() {
+            self.cells.remove(&point);
+        } else {
+            self.cells.insert(point, cell);
+        }
+    }
+}
+
+impl<T: Cell> FromStr for SparseGrid<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_grid(s, "grid")
+    }
+}
+
+impl<T: Cell> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#..\n.#.\n..#\n#..\n";
+
+    #[test]
+    fn parse_and_render() {
+        let dense: DenseGrid<bool> = MAP.parse().unwrap();
+        let sparse: SparseGrid<bool> = MAP.parse().unwrap();
+        assert_eq!((dense.width(), dense.height()), (3, 4));
+        assert_eq!((sparse.width(), sparse.height()), (3, 4));
+        assert_eq!(dense.to_string(), MAP);
+        assert_eq!(sparse.to_string(), MAP);
+        assert_eq!(sparse.cells().count(), 4);
+        assert_eq!(dense.count(true), 4);
+    }
+
+    #[test]
+    fn ragged_lines_are_padded() {
+        let grid: DenseGrid<bool> = "#\n.#.\n".parse().unwrap();
+        assert_eq!(grid.to_string(), "#..\n.#.\n");
+    }
+
+    #[test]
+    fn invalid_character() {
+        let err = "#.\n.x\n".parse::<DenseGrid<bool>>().unwrap_err();
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(err.column(), Some(2));
+    }
+
+    #[test]
+    fn neighbours_inside_grid() {
+        let grid: DenseGrid<bool> = MAP.parse().unwrap();
+        let corner = Point::new(0, 0);
+        assert_eq!(grid.neighbours(corner, Neighbourhood::Orthogonal).len(), 2);
+        assert_eq!(grid.neighbours(corner, Neighbourhood::All).len(), 3);
+        let middle = Point::new(1, 1);
+        assert_eq!(grid.neighbours(middle, Neighbourhood::All).len(), 8);
+    }
+
+    #[test]
+    fn wrapping() {
+        let grid: DenseGrid<bool> = MAP.parse().unwrap();
+        assert_eq!(grid.wrap(Point::new(-1, 5)), Point::new(2, 1));
+        assert!(grid.get_wrapping(Point::new(4, 5)));
+        assert_eq!(grid.get(Point::new(4, 5)), None);
+    }
+
+    #[test]
+    fn line_of_sight() {
+        let grid: DenseGrid<bool> = MAP.parse().unwrap();
+        let start = Point::new(0, 0);
+        let empty = |cell: bool| !cell;
+        assert_eq!(
+            grid.line_of_sight(start, (1, 1), empty),
+            Some(Point::new(1, 1))
+        );
+        assert_eq!(
+            grid.line_of_sight(start, (0, 1), empty),
+            Some(Point::new(0, 3))
+        );
+        assert_eq!(grid.line_of_sight(start, (1, 0), empty), None);
+    }
+}
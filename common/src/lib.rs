@@ -0,0 +1,117 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+pub mod automaton;
+pub mod check;
+mod error;
+pub mod grid;
+pub mod runner;
+pub mod samples;
+
+pub use error::{Error, ErrorKind, ParseError};
+
+/// A day's puzzle solution: parses the puzzle input once and solves both
+/// parts from the parsed input.
+pub trait Solution {
+    const DAY: u32;
+    /// Number of parts of the puzzle. The last day has a single one, and
+    /// its `part2` is never called.
+    const PARTS: u32 = 2;
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error>;
+}
+
+/// Parses every line of the input, reporting the line number of the first
+/// line that fails to parse.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    parse_lines_with(input, str::parse)
+}
+
+/// Parses every line of the input with the given function, reporting the
+/// line number of the first line that fails to parse.
+pub fn parse_lines_with<T, F>(
+    input: &str,
+    parse: F,
+) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .zip(1..)
+        .map(|(line, line_num)| {
+            parse(line).map_err(|err| err.at_line(line_num))
+        })
+        .collect()
+}
+
+/// Parses a number, reporting the token as an invalid `what` if it fails.
+pub fn parse_number<T: FromStr>(
+    token: &str,
+    what: &'static str,
+) -> Result<T, ParseError> {
+    token.parse().map_err(|_| {
+        ParseError::new(ErrorKind::InvalidNumber(what)).with_token(token)
+    })
+}
+
+/// Column (starting at 1) where `token` starts in `line`. The token must be a
+/// slice of the line, e.g. as returned by `split` or `trim`.
+pub fn column_of(line: &str, token: &str) -> usize {
+    token.as_ptr() as usize - line.as_ptr() as usize + 1
+}
+
+/// Splits a line on whitespace, pairing each token with its column.
+pub fn tokens(line: &str) -> impl Iterator<Item = (&str, usize)> {
+    line.split_whitespace()
+        .map(move |token| (token, column_of(line, token)))
+}
+
+/// A block of consecutive non-blank lines in the puzzle input.
+pub struct Section<'a> {
+    pub text: &'a str,
+    pub line_num: usize,
+}
+
+impl<'a> Section<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = (&'a str, usize)> {
+        self.text.lines().zip(self.line_num..)
+    }
+}
+
+/// Splits the input into sections separated by one or more blank lines.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for (line, line_num) in input.split_inclusive('\n').zip(1..) {
+        if line.trim().is_empty() {
+            if let Some((start_offset, start_line)) = start.take() {
+                sections.push(Section {
+                    text: input[start_offset..offset].trim_end(),
+                    line_num: start_line,
+                });
+            }
+        } else if start.is_none() {
+            start = Some((offset, line_num));
+        }
+        offset += line.len();
+    }
+    if let Some((start_offset, start_line)) = start {
+        sections.push(Section {
+            text: input[start_offset..].trim_end(),
+            line_num: start_line,
+        });
+    }
+
+    sections
+}
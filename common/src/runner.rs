@@ -0,0 +1,511 @@
+use crate::check::{check_results, Expected};
+use crate::{Error, ParseError, Solution};
+use clap::{App, Arg, ArgMatches};
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::fs::read_to_string;
+use std::io::{stdin, Read};
+use std::process::exit;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// How results are printed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!("Invalid format '{}'", s)),
+        }
+    }
+}
+
+/// Which parts of the puzzle to solve
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Parts {
+    Part1,
+    Part2,
+    Both,
+}
+
+impl Parts {
+    fn includes(self, part: u32) -> bool {
+        match self {
+            Self::Part1 => part == 1,
+            Self::Part2 => part == 2,
+            Self::Both => true,
+        }
+    }
+}
+
+impl FromStr for Parts {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Self::Part1),
+            "2" => Ok(Self::Part2),
+            "both" => Ok(Self::Both),
+            _ => Err(format!("Invalid part '{}'", s)),
+        }
+    }
+}
+
+/// How to solve each puzzle input
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    pub parts: Parts,
+    /// Number of times each part is solved, to get stable timings
+    pub repeat: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            parts: Parts::Both,
+            repeat: 1,
+        }
+    }
+}
+
+/// The answer to one part of a puzzle and how long it took to find it
+pub struct PartResult {
+    pub part: u32,
+    pub answer: Result<String, Error>,
+    /// Time taken by each run, from fastest to slowest
+    pub times: Vec<Duration>,
+}
+
+impl PartResult {
+    pub fn min(&self) -> Duration {
+        self.times[0]
+    }
+
+    pub fn median(&self) -> Duration {
+        self.times[self.times.len() / 2]
+    }
+
+    pub fn max(&self) -> Duration {
+        self.times[self.times.len() - 1]
+    }
+}
+
+/// Input name that stands for the standard input
+pub const STDIN: &str = "-";
+
+/// Why an input could not be used
+#[derive(Debug)]
+pub enum InputError {
+    /// The file could not be read
+    Read(String),
+    /// The contents are not a valid puzzle input
+    Parse(ParseError),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(err) => write!(f, "failed to read input: {}", err),
+            Self::Parse(err) => write!(f, "failed to parse input: {}", err),
+        }
+    }
+}
+
+/// The answers for one day's input, or why the input could not be used
+pub struct DayResult {
+    pub day: u32,
+    pub input: String,
+    pub parts: Result<Vec<PartResult>, InputError>,
+}
+
+impl DayResult {
+    pub fn failed(&self) -> bool {
+        self.parts.is_err()
+    }
+}
+
+/// Parses the input and solves the selected parts, timing each part
+/// separately.
+pub fn solve<S: Solution>(
+    input: &str,
+    options: &Options,
+) -> Result<Vec<PartResult>, ParseError> {
+    let input = S::parse(input)?;
+    let mut results = Vec::new();
+    if options.parts.includes(1) {
+        results.push(time_part(1, options.repeat, || {
+            S::part1(&input).map(|answer| answer.to_string())
+        }));
+    }
+    if options.parts.includes(2) && S::PARTS > 1 {
+        results.push(time_part(2, options.repeat, || {
+            S::part2(&input).map(|answer| answer.to_string())
+        }));
+    }
+    Ok(results)
+}
+
+fn time_part<F>(part: u32, repeat: usize, solve: F) -> PartResult
+where
+    F: Fn() -> Result<String, Error>,
+{
+    let mut times = Vec::new();
+    let mut run = || {
+        let start = Instant::now();
+        let answer = solve();
+        times.push(start.elapsed());
+        answer
+    };
+    let answer = run();
+    for _ in 1..repeat {
+        let _ = run();
+    }
+    times.sort();
+    PartResult {
+        part,
+        answer,
+        times,
+    }
+}
+
+/// Reads a puzzle input from a file, or from the standard input if the
+/// filename is `-`.
+pub fn read_input(filename: &str) -> Result<String, String> {
+    let mut input = String::new();
+    if filename == STDIN {
+        stdin().read_to_string(&mut input).map(|_| input)
+    } else {
+        read_to_string(filename)
+    }
+    .map_err(|err| err.to_string())
+}
+
+/// Reads an input file like [`read_input`], then parses it.
+pub fn parse_file<T, F>(filename: &str, parse: F) -> Result<T, InputError>
+where
+    F: FnOnce(&str) -> Result<T, ParseError>,
+{
+    let input = read_input(filename).map_err(InputError::Read)?;
+    parse(&input).map_err(InputError::Parse)
+}
+
+/// Reads the input file and solves the puzzle for the given day.
+pub fn run_file<S: Solution>(filename: &str, options: &Options) -> DayResult {
+    let parts = parse_file(filename, |input| solve::<S>(input, options));
+    DayResult {
+        day: S::DAY,
+        input: filename.to_string(),
+        parts,
+    }
+}
+
+/// Checks that the standard input is not given more than once, as it can
+/// only be read once.
+pub fn check_inputs<'a, I>(filenames: I) -> Result<(), String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    if filenames.into_iter().filter(|&name| name == STDIN).count() > 1 {
+        Err("The standard input can only be given once".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Adds the options shared by every binary to a command line app.
+pub fn shared_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("format")
+            .long("format")
+            .value_name("FORMAT")
+            .possible_values(&["text", "json", "csv"])
+            .default_value("text")
+            .help("Output format"),
+    )
+    .arg(
+        Arg::with_name("part")
+            .long("part")
+            .value_name("PART")
+            .possible_values(&["1", "2", "both"])
+            .default_value("both")
+            .help("Part of the puzzle to solve"),
+    )
+    .arg(
+        Arg::with_name("repeat")
+            .long("repeat")
+            .value_name("N")
+            .default_value("1")
+            .validator(|value| match value.parse::<usize>() {
+                Ok(repeat) if repeat > 0 => Ok(()),
+                _ => Err("must be a positive number".to_string()),
+            })
+            .help(
+                "Number of times to solve each part, timings show the \
+                   min, median and max",
+            ),
+    )
+    .arg(
+        Arg::with_name("check")
+            .long("check")
+            .value_name("FILE")
+            .conflicts_with("format")
+            .help("Compares answers with the expected ones in a TOML file"),
+    )
+}
+
+pub fn format_arg(args: &ArgMatches) -> Format {
+    args.value_of("format").unwrap().parse().unwrap()
+}
+
+pub fn options_arg(args: &ArgMatches) -> Options {
+    Options {
+        parts: args.value_of("part").unwrap().parse().unwrap(),
+        repeat: args.value_of("repeat").unwrap().parse().unwrap(),
+    }
+}
+
+/// Command line app of a day's binary, taking puzzle inputs as arguments
+/// along with the shared options. Days with options of their own add them
+/// here before calling [`run`].
+pub fn app(description: &str) -> App<'_, '_> {
+    shared_args(
+        App::new(description).arg(
+            Arg::with_name("INPUT")
+                .help("Files with puzzle input, or '-' for standard input")
+                .required(true)
+                .multiple(true)
+                .index(1),
+        ),
+    )
+}
+
+/// Command line entry point of a day's binary: solves every puzzle input
+/// given as argument and prints the results.
+pub fn main<S: Solution>(description: &str) {
+    run::<S>(&app(description).get_matches());
+}
+
+/// Solves every puzzle input in the parsed arguments of [`app`] and reports
+/// the results.
+pub fn run<S: Solution>(args: &ArgMatches) {
+    let filenames = input_args(args);
+    let options = options_arg(args);
+    let results: Vec<_> = filenames
+        .iter()
+        .map(|filename| run_file::<S>(filename, &options))
+        .collect();
+    report(&results, args);
+}
+
+/// The puzzle inputs given as arguments to [`app`], exiting with an error if
+/// they can't all be read.
+pub fn input_args<'a>(args: &'a ArgMatches) -> Vec<&'a str> {
+    let filenames: Vec<_> = args.values_of("INPUT").unwrap().collect();
+    if let Err(err) = check_inputs(filenames.iter().copied()) {
+        println!("{}", err);
+        exit(2);
+    }
+    filenames
+}
+
+/// Prints the results, or checks them against the expected answers if
+/// requested, and exits with an error if any input could not be used or any
+/// answer was wrong.
+pub fn report(results: &[DayResult], args: &ArgMatches) {
+    let checked = args.value_of("check").map(|filename| {
+        let expected = Expected::load(filename).unwrap_or_else(|err| {
+            println!("Failed to read expected answers: {}", err);
+            exit(2);
+        });
+        check_results(results, &expected)
+    });
+    if checked.is_none() {
+        print_results(results, format_arg(args));
+    }
+
+    if results.iter().any(DayResult::failed) {
+        exit(2);
+    }
+    if checked == Some(false) {
+        exit(1);
+    }
+}
+
+pub fn print_results(results: &[DayResult], format: Format) {
+    match format {
+        Format::Text => print_text(results),
+        Format::Json => print_json(results),
+        Format::Csv => print_csv(results),
+    }
+}
+
+fn print_text(results: &[DayResult]) {
+    for (index, result) in results.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        let same_day = results.iter().filter(|other| other.day == result.day);
+        // Tell apart the inputs when the same day is solved more than once
+        if same_day.count() > 1 {
+            let input = match result.input.as_str() {
+                STDIN => "standard input",
+                filename => filename,
+            };
+            println!("Advent of Code 2020 - Day {:02} - {}", result.day, input);
+        } else {
+            println!("Advent of Code 2020 - Day {:02}", result.day);
+        }
+        match &result.parts {
+            Ok(parts) => {
+                for part in parts {
+                    let answer = match &part.answer {
+                        Ok(answer) => answer.clone(),
+                        Err(err) => err.to_string(),
+                    };
+                    if part.times.len() > 1 {
+                        println!(
+                            "Part {}: {} (min {}, median {}, max {} over {} \
+                             runs)",
+                            part.part,
+                            answer,
+                            format_duration(part.min()),
+                            format_duration(part.median()),
+                            format_duration(part.max()),
+                            part.times.len()
+                        );
+                    } else {
+                        println!("Part {}: {}", part.part, answer);
+                    }
+                }
+            }
+            Err(InputError::Read(err)) => {
+                println!("Failed to read input: {}", err)
+            }
+            Err(InputError::Parse(err)) => {
+                println!("Failed to parse input: {}", err)
+            }
+        }
+    }
+}
+
+/// One row of machine-readable output: a part's answer, or a failure to
+/// read the input when there's no part. The elapsed time is the median of
+/// all runs.
+#[derive(Serialize)]
+struct Row<'a> {
+    day: u32,
+    input: &'a str,
+    part: Option<u32>,
+    answer: Option<&'a str>,
+    status: &'static str,
+    message: Option<String>,
+    runs: usize,
+    elapsed_ms: Option<f64>,
+    min_ms: Option<f64>,
+    max_ms: Option<f64>,
+}
+
+fn rows(results: &[DayResult]) -> Vec<Row<'_>> {
+    let mut rows = Vec::new();
+    for result in results {
+        match &result.parts {
+            Ok(parts) => rows.extend(parts.iter().map(|part| {
+                let (answer, status, message) = match &part.answer {
+                    Ok(answer) => (Some(answer.as_str()), "ok", None),
+                    Err(Error::NotFound) => (None, "not found", None),
+                    Err(err) => (None, "error", Some(err.to_string())),
+                };
+                Row {
+                    day: result.day,
+                    input: &result.input,
+                    part: Some(part.part),
+                    answer,
+                    status,
+                    message,
+                    runs: part.times.len(),
+                    elapsed_ms: Some(millis(part.median())),
+                    min_ms: Some(millis(part.min())),
+                    max_ms: Some(millis(part.max())),
+                }
+            })),
+            Err(err) => rows.push(Row {
+                day: result.day,
+                input: &result.input,
+                part: None,
+                answer: None,
+                status: "error",
+                message: Some(err.to_string()),
+                runs: 0,
+                elapsed_ms: None,
+                min_ms: None,
+                max_ms: None,
+            }),
+        }
+    }
+    rows
+}
+
+fn print_json(results: &[DayResult]) {
+    println!("{}", serde_json::to_string_pretty(&rows(results)).unwrap());
+}
+
+fn print_csv(results: &[DayResult]) {
+    println!(
+        "day,input,part,answer,status,message,runs,elapsed_ms,min_ms,max_ms"
+    );
+    for row in rows(results) {
+        let millis = |time: Option<f64>| {
+            time.map(|time| format!("{:.3}", time)).unwrap_or_default()
+        };
+        println!(
+            "{},{},{},{},{},{},{},{},{},{}",
+            row.day,
+            csv_field(row.input),
+            row.part.map(|part| part.to_string()).unwrap_or_default(),
+            csv_field(row.answer.unwrap_or_default()),
+            row.status,
+            csv_field(&row.message.unwrap_or_default()),
+            row.runs,
+            millis(row.elapsed_ms),
+            millis(row.min_ms),
+            millis(row.max_ms),
+        );
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn millis(time: Duration) -> f64 {
+    time.as_nanos() as f64 / 1e6
+}
+
+/// Formats a duration with a unit that suits its magnitude.
+pub fn format_duration(time: Duration) -> String {
+    let nanos = time.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
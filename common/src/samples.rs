@@ -0,0 +1,100 @@
+use crate::Solution;
+use serde::Deserialize;
+use std::fs::read_to_string;
+use std::path::Path;
+
+const MANIFEST: &str = "answers.toml";
+
+/// An example input published with the puzzle, along with the expected
+/// answer of each part that has one.
+#[derive(Deserialize)]
+pub struct Sample {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    sample: Vec<Sample>,
+}
+
+/// Reads the samples listed in the `answers.toml` manifest of a directory.
+pub fn load_samples(dir: &Path) -> Result<Vec<Sample>, String> {
+    let manifest = read_to_string(dir.join(MANIFEST))
+        .map_err(|err| format!("Failed to read {}: {}", MANIFEST, err))?;
+    toml::from_str::<Manifest>(&manifest)
+        .map(|manifest| manifest.sample)
+        .map_err(|err| format!("Invalid {}: {}", MANIFEST, err))
+}
+
+/// Solves every sample in the directory, panicking with a list of all the
+/// answers that don't match the manifest.
+pub fn check_samples<S: Solution>(dir: &Path) {
+    let samples = load_samples(dir).unwrap_or_else(|err| panic!("{}", err));
+    let mut failures = Vec::new();
+
+    for sample in samples.iter() {
+        let input = read_to_string(dir.join(&sample.input))
+            .unwrap_or_else(|err| panic!("{}: {}", sample.input, err));
+        let input = match S::parse(&input) {
+            Ok(input) => input,
+            Err(err) => {
+                failures.push(format!("{}: {}", sample.input, err));
+                continue;
+            }
+        };
+        if let Some(expected) = &sample.part1 {
+            let answer = S::part1(&input).map(|answer| answer.to_string());
+            check_answer(&mut failures, &sample.input, 1, expected, answer);
+        }
+        if let Some(expected) = &sample.part2 {
+            if S::PARTS < 2 {
+                failures.push(format!(
+                    "{}: the puzzle has no part 2",
+                    sample.input
+                ));
+                continue;
+            }
+            let answer = S::part2(&input).map(|answer| answer.to_string());
+            check_answer(&mut failures, &sample.input, 2, expected, answer);
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+fn check_answer(
+    failures: &mut Vec<String>,
+    sample: &str,
+    part: u32,
+    expected: &str,
+    answer: Result<String, crate::Error>,
+) {
+    match answer {
+        Ok(answer) if answer == expected => (),
+        Ok(answer) => failures.push(format!(
+            "{} part {}: expected {} but got {}",
+            sample, part, expected, answer
+        )),
+        Err(err) => failures.push(format!(
+            "{} part {}: expected {} but got error: {}",
+            sample, part, expected, err
+        )),
+    }
+}
+
+/// Defines a `samples` test that checks the samples in the `samples`
+/// directory of the crate using it, e.g. `sample_tests!(day07::Day07);`.
+#[macro_export]
+macro_rules! sample_tests {
+    ($solution:ty) => {
+        #[test]
+        fn samples() {
+            $crate::samples::check_samples::<$solution>(
+                &::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("samples"),
+            );
+        }
+    };
+}
@@ -0,0 +1,222 @@
+//! A readable form of programs of the puzzle's instruction set, where jumps
+//! go to labels instead of relative positions.
+//!
+//! Each line has an instruction, a label followed by a colon, or nothing.
+//! Labels stand for the position of the next instruction, or for the end of
+//! the program after the last one. Comments start with `;`.
+//!
+//! ```text
+//! start:
+//!     acc +1
+//!     jmp start   ; loops forever
+//! ```
+
+use crate::flow::{target, Flow, Target};
+use crate::Op;
+use common::{parse_number, tokens, ErrorKind, ParseError};
+use std::collections::{HashMap, HashSet};
+
+/// Label of the block starting at the given position, or of the end of the
+/// program.
+fn label(target: Target) -> Option<String> {
+    match target {
+        Target::Instruction(position) => Some(format!("L{}", position)),
+        Target::End => Some("end".to_string()),
+        Target::Outside(_) => None,
+    }
+}
+
+/// Writes the program as labelled basic blocks, with jumps going to labels
+/// unless they leave the program.
+pub fn disassemble(program: &[Op]) -> String {
+    let flow = Flow::new(program);
+    let mut text = String::new();
+    let mut jumps_to_end = false;
+    for block in flow.blocks() {
+        text.push_str(&label(Target::Instruction(block.start)).unwrap());
+        text.push_str(":\n");
+        for (&op, position) in
+            program[block.start..block.end].iter().zip(block.start..)
+        {
+            let target = target(op, position, program.len());
+            match (op, label(target)) {
+                (Op::Jmp(_), Some(label)) => {
+                    jumps_to_end |= target == Target::End;
+                    text.push_str(&format!("    jmp {}\n", label));
+                }
+                _ => text.push_str(&format!("    {}\n", op)),
+            }
+        }
+    }
+    if jumps_to_end {
+        text.push_str("end:\n");
+    }
+    text
+}
+
+/// The argument of an instruction, before labels are known
+enum Argument<'a> {
+    Number(i64),
+    Label(&'a str, usize),
+}
+
+fn is_label(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+/// Parses a program with labels, returning it in the puzzle's form.
+pub fn assemble(source: &str) -> Result<Vec<Op>, ParseError> {
+    let mut labels = HashMap::new();
+    let mut seen = HashSet::new();
+    let mut instructions = Vec::new();
+
+    for (line, line_num) in source.lines().zip(1..) {
+        let code = line.split(';').next().unwrap();
+        let tokens: Vec<_> = tokens(code).collect();
+        let error = |kind, token: &str, column| {
+            ParseError::new(kind)
+                .with_token(token)
+                .with_column(column)
+                .at_line(line_num)
+        };
+        match tokens.as_slice() {
+            [] => (),
+            [(token, column)] if token.ends_with(':') => {
+                let name = token.trim_end_matches(':');
+                if !is_label(name) {
+                    return Err(error(
+                        ErrorKind::InvalidSyntax("label"),
+                        name,
+                        *column,
+                    ));
+                }
+                if !seen.insert(name) {
+                    return Err(error(
+                        ErrorKind::InvalidValue("duplicate label"),
+                        name,
+                        *column,
+                    ));
+                }
+                labels.insert(name, instructions.len());
+            }
+            [(op, op_column), (arg, arg_column)] => {
+                if !["acc", "jmp", "nop"].contains(op) {
+                    return Err(error(
+                        ErrorKind::InvalidValue("unknown operation"),
+                        op,
+                        *op_column,
+                    ));
+                }
+                let argument = if is_label(arg) {
+                    if *op == "acc" {
+                        return Err(error(
+                            ErrorKind::InvalidValue("acc takes a number"),
+                            arg,
+                            *arg_column,
+                        ));
+                    }
+                    Argument::Label(arg, *arg_column)
+                } else {
+                    Argument::Number(parse_number(arg, "argument").map_err(
+                        |err| err.with_column(*arg_column).at_line(line_num),
+                    )?)
+                };
+                instructions.push((*op, argument, line_num));
+            }
+            _ => {
+                return Err(ParseError::new(ErrorKind::InvalidSyntax(
+                    "instruction",
+                ))
+                .with_token(code.trim())
+                .at_line(line_num))
+            }
+        }
+    }
+
+    instructions
+        .into_iter()
+        .enumerate()
+        .map(|(position, (op, argument, line_num))| {
+            let arg = match argument {
+                Argument::Number(arg) => arg,
+                Argument::Label(name, column) => {
+                    let target = labels.get(name).ok_or_else(|| {
+                        ParseError::new(ErrorKind::InvalidValue(
+                            "unknown label",
+                        ))
+                        .with_token(name)
+                        .with_column(column)
+                        .at_line(line_num)
+                    })?;
+                    *target as i64 - position as i64
+                }
+            };
+            Ok(match op {
+                "acc" => Op::Acc(arg),
+                "jmp" => Op::Jmp(arg),
+                _ => Op::Nop(arg),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\n\
+                           acc -99\nacc +1\njmp -4\nacc +6\n";
+
+    fn program(text: &str) -> Vec<Op> {
+        text.lines().map(|line| line.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn disassemble_example() {
+        assert_eq!(
+            disassemble(&program(EXAMPLE)),
+            "L0:\n    nop +0\n\
+             L1:\n    acc +1\n    jmp L6\n\
+             L3:\n    acc +3\n    jmp L1\n\
+             L5:\n    acc -99\n\
+             L6:\n    acc +1\n    jmp L3\n\
+             L8:\n    acc +6\n"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let program = program(EXAMPLE);
+        assert_eq!(assemble(&disassemble(&program)).unwrap(), program);
+        let program = vec![Op::Jmp(3), Op::Jmp(-7), Op::Acc(1)];
+        assert_eq!(disassemble(&program).lines().last(), Some("end:"));
+        assert_eq!(assemble(&disassemble(&program)).unwrap(), program);
+    }
+
+    #[test]
+    fn assemble_labels() {
+        let source = "start:\n    acc +1 ; count\n\n    jmp start\n\
+                      nop done\ndone:\n";
+        assert_eq!(
+            assemble(source).unwrap(),
+            vec![Op::Acc(1), Op::Jmp(-1), Op::Nop(1)]
+        );
+    }
+
+    #[test]
+    fn assemble_errors() {
+        let err = assemble("acc +1\njmp nowhere\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue("unknown label"));
+        assert_eq!((err.line(), err.column()), (Some(2), Some(5)));
+        let err = assemble("a:\nacc +1\na:\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue("duplicate label"));
+        assert_eq!(err.line(), Some(3));
+        assert!(assemble("mul +2\n").is_err());
+        assert!(assemble("acc start\nstart:\n").is_err());
+        assert!(assemble("jmp +1 +2\n").is_err());
+    }
+}
@@ -0,0 +1,273 @@
+//! Static analysis of programs of the puzzle's instruction set: how control
+//! flows between basic blocks, which code can never run and which loops
+//! never end.
+
+use crate::Op;
+use std::collections::{BTreeSet, HashSet};
+use std::convert::TryFrom;
+use std::fmt::Write;
+
+/// Where control goes after an instruction
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Target {
+    /// The instruction at this position
+    Instruction(usize),
+    /// Just past the last instruction, where the program terminates
+    End,
+    /// Outside the program, at this position
+    Outside(i64),
+}
+
+/// Where control goes after executing the instruction at `position` in a
+/// program of `len` instructions.
+pub fn target(op: Op, position: usize, len: usize) -> Target {
+    let offset = match op {
+        Op::Jmp(arg) => arg,
+        Op::Acc(_) | Op::Nop(_) => 1,
+    };
+    let next = (position as i64).saturating_add(offset);
+    match usize::try_from(next) {
+        Ok(next) if next < len => Target::Instruction(next),
+        Ok(next) if next == len => Target::End,
+        _ => Target::Outside(next),
+    }
+}
+
+/// Consecutive instructions that always run one after the other: only the
+/// first can be jumped to, and only the last can jump
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Block {
+    /// Position of the first instruction
+    pub start: usize,
+    /// Position just past the last instruction
+    pub end: usize,
+    /// Where control goes after the block
+    pub next: Target,
+}
+
+/// The control flow graph of a program
+pub struct Flow {
+    blocks: Vec<Block>,
+}
+
+impl Flow {
+    pub fn new(program: &[Op]) -> Self {
+        let len = program.len();
+        let mut leaders = BTreeSet::new();
+        if len > 0 {
+            leaders.insert(0);
+        }
+        for (position, &op) in program.iter().enumerate() {
+            if let Op::Jmp(_) = op {
+                if let Target::Instruction(next) = target(op, position, len) {
+                    leaders.insert(next);
+                }
+                if position + 1 < len {
+                    leaders.insert(position + 1);
+                }
+            }
+        }
+
+        let starts: Vec<_> = leaders.into_iter().collect();
+        let blocks = starts
+            .iter()
+            .enumerate()
+            .map(|(index, &start)| {
+                let end = starts.get(index + 1).copied().unwrap_or(len);
+                Block {
+                    start,
+                    end,
+                    next: target(program[end - 1], end - 1, len),
+                }
+            })
+            .collect();
+        Self { blocks }
+    }
+
+    /// Basic blocks, in program order
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    /// The block starting at the given position.
+    pub fn block_at(&self, start: usize) -> Option<&Block> {
+        self.blocks
+            .binary_search_by_key(&start, |block| block.start)
+            .ok()
+            .map(|index| &self.blocks[index])
+    }
+
+    /// Blocks run when the program starts, in order, until the program
+    /// terminates, leaves the program or comes back to a block it already
+    /// ran.
+    pub fn path(&self) -> Vec<&Block> {
+        let mut path = Vec::new();
+        let mut seen = HashSet::new();
+        let mut block = self.blocks.first();
+        while let Some(current) = block {
+            if !seen.insert(current.start) {
+                break;
+            }
+            path.push(current);
+            block = match current.next {
+                Target::Instruction(next) => self.block_at(next),
+                Target::End | Target::Outside(_) => None,
+            };
+        }
+        path
+    }
+
+    /// Blocks that can never run, as nothing leads to them from the start
+    pub fn unreachable(&self) -> Vec<&Block> {
+        let reachable: HashSet<_> =
+            self.path().iter().map(|block| block.start).collect();
+        self.blocks
+            .iter()
+            .filter(|block| !reachable.contains(&block.start))
+            .collect()
+    }
+
+    /// Every set of blocks that run one after another forever once any of
+    /// them runs, each starting with its first block in program order.
+    pub fn infinite_loops(&self) -> Vec<Vec<&Block>> {
+        let mut loops = Vec::new();
+        // Blocks whose way forward is known not to lead to a new loop
+        let mut done = HashSet::new();
+        for first in self.blocks.iter() {
+            let mut way = Vec::new();
+            let mut block = Some(first);
+            while let Some(current) = block {
+                if done.contains(&current.start) {
+                    // Either a new loop closing on this way, or an old one
+                    if let Some(index) = way
+                        .iter()
+                        .position(|b: &&Block| b.start == current.start)
+                    {
+                        let mut cycle = way.split_off(index);
+                        let first_index = cycle
+                            .iter()
+                            .enumerate()
+                            .min_by_key(|(_, block)| block.start)
+                            .map(|(index, _)| index)
+                            .unwrap();
+                        cycle.rotate_left(first_index);
+                        loops.push(cycle);
+                    }
+                    break;
+                }
+                done.insert(current.start);
+                way.push(current);
+                block = match current.next {
+                    Target::Instruction(next) => self.block_at(next),
+                    Target::End | Target::Outside(_) => None,
+                };
+            }
+        }
+        loops.sort_by_key(|cycle| cycle[0].start);
+        loops
+    }
+
+    /// Whether the program terminates when run from the start.
+    pub fn terminates(&self) -> bool {
+        match self.path().last() {
+            Some(block) => block.next == Target::End,
+            None => true,
+        }
+    }
+
+    /// Writes the graph in the DOT language, with a node per block listing
+    /// its instructions.
+    pub fn to_dot(&self, program: &[Op]) -> String {
+        let mut dot = String::from("digraph program {\n");
+        dot.push_str("    node [shape=box, fontname=monospace];\n");
+        for block in self.blocks.iter() {
+            let code: String = program[block.start..block.end]
+                .iter()
+                .zip(block.start..)
+                .map(|(op, position)| format!("{}: {}\\l", position, op))
+                .collect();
+            writeln!(dot, "    b{} [label=\"{}\"];", block.start, code)
+                .unwrap();
+        }
+        dot.push_str("    end [shape=doublecircle];\n");
+        for block in self.blocks.iter() {
+            let next = match block.next {
+                Target::Instruction(next) => format!("b{}", next),
+                Target::End => "end".to_string(),
+                Target::Outside(next) => {
+                    writeln!(
+                        dot,
+                        "    out{} [label=\"{}\", shape=octagon];",
+                        block.start, next
+                    )
+                    .unwrap();
+                    format!("out{}", block.start)
+                }
+            };
+            writeln!(dot, "    b{} -> {};", block.start, next).unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program(text: &str) -> Vec<Op> {
+        text.lines().map(|line| line.parse().unwrap()).collect()
+    }
+
+    fn starts(blocks: &[&Block]) -> Vec<usize> {
+        blocks.iter().map(|block| block.start).collect()
+    }
+
+    #[test]
+    fn blocks() {
+        let program = program(
+            "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\n\
+                               acc -99\nacc +1\njmp -4\nacc +6\n",
+        );
+        let flow = Flow::new(&program);
+        let blocks: Vec<_> = flow.blocks().iter().collect();
+        assert_eq!(starts(&blocks), vec![0, 1, 3, 5, 6, 8]);
+        assert_eq!(flow.blocks()[0].next, Target::Instruction(1));
+        assert_eq!(flow.blocks()[1].next, Target::Instruction(6));
+        assert_eq!(flow.blocks()[3].next, Target::Instruction(6));
+        assert_eq!(flow.blocks()[5].next, Target::End);
+        assert_eq!(starts(&flow.path()), vec![0, 1, 6, 3]);
+        assert_eq!(starts(&flow.unreachable()), vec![5, 8]);
+        let loops = flow.infinite_loops();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(starts(&loops[0]), vec![1, 6, 3]);
+        assert!(!flow.terminates());
+    }
+
+    #[test]
+    fn terminates() {
+        let flow = Flow::new(&program("acc +1\njmp +2\njmp +0\nnop +0\n"));
+        assert!(flow.terminates());
+        assert_eq!(starts(&flow.unreachable()), vec![2]);
+        assert_eq!(flow.infinite_loops().len(), 1);
+        let flow = Flow::new(&program("acc +1\njmp -2\n"));
+        assert!(!flow.terminates());
+        assert!(flow.infinite_loops().is_empty());
+        assert_eq!(flow.blocks()[0].next, Target::Outside(-1));
+    }
+
+    #[test]
+    fn dot() {
+        let program = program("acc +1\njmp -2\n");
+        assert_eq!(
+            Flow::new(&program).to_dot(&program),
+            "digraph program {\n\
+             \x20   node [shape=box, fontname=monospace];\n\
+             \x20   b0 [label=\"0: acc +1\\l1: jmp -2\\l\"];\n\
+             \x20   end [shape=doublecircle];\n\
+             \x20   out0 [label=\"-1\", shape=octagon];\n\
+             \x20   b0 -> out0;\n\
+             }\n"
+        );
+    }
+}
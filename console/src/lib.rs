@@ -0,0 +1,556 @@
+//! The handheld game console from day 8: a small virtual machine running
+//! programs of instructions, one at a time, with a program counter and a set
+//! of registers.
+//!
+//! The instruction set is pluggable through the [`Instruction`] trait, with
+//! the puzzle's own in [`Op`]. Programs of the puzzle's instruction set can
+//! also be analysed without running them with [`flow`], and written with
+//! labels with [`asm`].
+
+#[macro_use]
+extern crate lazy_static;
+
+pub mod asm;
+pub mod flow;
+mod op;
+
+pub use op::Op;
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
+
+/// Index of the accumulator, the first register
+pub const ACC: usize = 0;
+
+/// State of the console's processor
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cpu {
+    /// Index of the next instruction to execute
+    pub pc: i64,
+    registers: Vec<i64>,
+}
+
+impl Cpu {
+    /// A processor at the start of the program with the given number of
+    /// registers, all set to 0.
+    pub fn new(registers: usize) -> Self {
+        Self {
+            pc: 0,
+            registers: vec![0; registers],
+        }
+    }
+
+    pub fn acc(&self) -> i64 {
+        self.registers[ACC]
+    }
+
+    pub fn register(&self, index: usize) -> i64 {
+        self.registers[index]
+    }
+
+    pub fn register_mut(&mut self, index: usize) -> &mut i64 {
+        &mut self.registers[index]
+    }
+
+    pub fn registers(&self) -> &[i64] {
+        &self.registers
+    }
+}
+
+/// An instruction that the console can execute
+pub trait Instruction: Display {
+    /// Short name of the operation, e.g. `jmp`
+    fn opcode(&self) -> &'static str;
+
+    /// Whether where a program goes next only depends on the program
+    /// counter, as with the puzzle's instructions, so that a program about to
+    /// execute an instruction for the second time loops forever. Machines
+    /// only stop on such loops when it's set, and programs looping on their
+    /// registers need a step limit instead.
+    const PC_DETERMINES_FLOW: bool = false;
+
+    /// Executes the instruction, updating the registers and moving the
+    /// program counter to the next instruction to execute. Fails when the
+    /// result doesn't fit in a register or the program counter.
+    fn execute(&self, cpu: &mut Cpu) -> Result<(), Error>;
+}
+
+/// Why the console can't go on running a program
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The instruction at `from` moved the program counter to `target`,
+    /// which is neither in the program nor just past its last instruction.
+    /// Targets beyond the range of `i64` are clamped to it.
+    JumpOutOfRange { from: usize, target: i64 },
+    /// The instruction at `pc` overflowed the register with this index
+    Overflow { pc: usize, register: usize },
+    /// A run was started on a machine that already stopped for good, as
+    /// the program halted, looped or jumped out of range, or whose program
+    /// counter was moved out of the program
+    Reentry { pc: i64 },
+    /// The given number of instructions were executed without the program
+    /// halting
+    StepLimit(usize),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::JumpOutOfRange { from, target } => write!(
+                f,
+                "instruction at {} jumped out of the program to {}",
+                from, target
+            ),
+            Error::Overflow { pc, register: ACC } => {
+                write!(f, "instruction at {} overflowed the accumulator", pc)
+            }
+            Error::Overflow { pc, register } => write!(
+                f,
+                "instruction at {} overflowed register {}",
+                pc, register
+            ),
+            Error::Reentry { pc } => {
+                write!(f, "can't run again from program counter {}", pc)
+            }
+            Error::StepLimit(limit) => {
+                write!(f, "step limit of {} instructions exceeded", limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// What happened after a single step
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    /// There are more instructions to execute
+    Running,
+    /// The program counter is just past the last instruction
+    Halted,
+}
+
+/// Why a run stopped
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Exit {
+    /// The program counter moved just past the last instruction
+    Halted,
+    /// The next instruction was already executed, and the instruction set
+    /// says that where programs go only depends on the program counter, so
+    /// the program would loop forever
+    Loop,
+    /// The next instruction has a breakpoint
+    Breakpoint,
+}
+
+/// An executed instruction, as recorded in the trace
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TraceEntry {
+    /// Number of instructions executed before this one
+    pub step: usize,
+    pub pc: usize,
+    pub instruction: String,
+    /// The accumulator after executing the instruction
+    pub acc: i64,
+}
+
+/// A console running a program
+pub struct Machine<'a, I> {
+    program: &'a [I],
+    cpu: Cpu,
+    steps: usize,
+    executed: HashSet<usize>,
+    breakpoints: HashSet<usize>,
+    /// Set when a run stops at a breakpoint, so that the next run goes
+    /// past it
+    paused_at: Option<usize>,
+    trace: Option<Vec<TraceEntry>>,
+    step_limit: Option<usize>,
+    /// Set once a run stopped for a reason other than a breakpoint or the
+    /// step limit
+    stopped: bool,
+}
+
+impl<'a, I: Instruction> Machine<'a, I> {
+    /// A console about to run the program, with just the accumulator.
+    pub fn new(program: &'a [I]) -> Self {
+        Self::with_registers(program, 1)
+    }
+
+    /// A console about to run the program, with the given number of
+    /// registers including the accumulator.
+    pub fn with_registers(program: &'a [I], registers: usize) -> Self {
+        Self {
+            program,
+            cpu: Cpu::new(registers.max(1)),
+            steps: 0,
+            executed: HashSet::new(),
+            breakpoints: HashSet::new(),
+            paused_at: None,
+            trace: None,
+            step_limit: None,
+            stopped: false,
+        }
+    }
+
+    pub fn cpu(&self) -> &Cpu {
+        &self.cpu
+    }
+
+    pub fn cpu_mut(&mut self) -> &mut Cpu {
+        &mut self.cpu
+    }
+
+    pub fn acc(&self) -> i64 {
+        self.cpu.acc()
+    }
+
+    /// Number of instructions executed so far
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Makes runs stop before executing the instruction at `pc`.
+    pub fn set_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.insert(pc);
+    }
+
+    pub fn clear_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.remove(&pc);
+    }
+
+    /// Makes the machine fail once it executed this many instructions in
+    /// total, without the program halting. There is no limit by default.
+    pub fn set_step_limit(&mut self, limit: Option<usize>) {
+        self.step_limit = limit;
+    }
+
+    /// Starts recording every instruction executed from now on.
+    pub fn record_trace(&mut self) {
+        self.trace.get_or_insert_with(Vec::new);
+    }
+
+    /// Instructions executed since recording started
+    pub fn trace(&self) -> &[TraceEntry] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    /// Writes the trace as tab-separated values, with a header line.
+    pub fn write_trace<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "step\tpc\tinstruction\tacc")?;
+        for entry in self.trace() {
+            writeln!(
+                out,
+                "{}\t{}\t{}\t{}",
+                entry.step, entry.pc, entry.instruction, entry.acc
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.cpu.pc == self.program.len() as i64
+    }
+
+    /// Index of the next instruction, if it's in the program.
+    fn next_instruction(&self) -> Option<usize> {
+        usize::try_from(self.cpu.pc)
+            .ok()
+            .filter(|&pc| pc < self.program.len())
+    }
+
+    /// Executes the next instruction, unless the program already halted.
+    pub fn step(&mut self) -> Result<Status, Error> {
+        if self.is_halted() {
+            return Ok(Status::Halted);
+        }
+        let pc = self
+            .next_instruction()
+            .ok_or(Error::Reentry { pc: self.cpu.pc })?;
+        if let Some(limit) =
+            self.step_limit.filter(|&limit| self.steps >= limit)
+        {
+            return Err(Error::StepLimit(limit));
+        }
+        let instruction = &self.program[pc];
+        instruction.execute(&mut self.cpu)?;
+        if let Some(trace) = self.trace.as_mut() {
+            trace.push(TraceEntry {
+                step: self.steps,
+                pc,
+                instruction: instruction.to_string(),
+                acc: self.cpu.acc(),
+            });
+        }
+        self.steps += 1;
+        if I::PC_DETERMINES_FLOW {
+            self.executed.insert(pc);
+        }
+        self.paused_at = None;
+
+        if self.is_halted() {
+            Ok(Status::Halted)
+        } else if self.next_instruction().is_some() {
+            Ok(Status::Running)
+        } else {
+            Err(Error::JumpOutOfRange {
+                from: pc,
+                target: self.cpu.pc,
+            })
+        }
+    }
+
+    /// Executes instructions until the program halts, is about to execute
+    /// an instruction for the second time when that means it loops forever,
+    /// or reaches a breakpoint. Running
+    /// again after a breakpoint or after hitting the step limit carries on
+    /// from there, but running again after anything else fails.
+    pub fn run(&mut self) -> Result<Exit, Error> {
+        if self.stopped {
+            return Err(Error::Reentry { pc: self.cpu.pc });
+        }
+        let exit = self.run_until_exit();
+        self.stopped =
+            !matches!(exit, Ok(Exit::Breakpoint) | Err(Error::StepLimit(_)));
+        exit
+    }
+
+    fn run_until_exit(&mut self) -> Result<Exit, Error> {
+        loop {
+            if self.is_halted() {
+                return Ok(Exit::Halted);
+            }
+            let pc = self
+                .next_instruction()
+                .ok_or(Error::Reentry { pc: self.cpu.pc })?;
+            if self.executed.contains(&pc) {
+                return Ok(Exit::Loop);
+            }
+            if self.breakpoints.contains(&pc) && self.paused_at != Some(pc) {
+                self.paused_at = Some(pc);
+                return Ok(Exit::Breakpoint);
+            }
+            self.step()?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program(text: &str) -> Vec<Op> {
+        text.lines().map(|line| line.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn run_until_loop() {
+        let program = program("nop +0\nacc +1\njmp +2\nacc +5\njmp -3\n");
+        let mut machine = Machine::new(&program);
+        assert_eq!(machine.run(), Ok(Exit::Loop));
+        assert_eq!(machine.acc(), 1);
+        assert_eq!(machine.cpu().pc, 1);
+    }
+
+    #[test]
+    fn single_steps() {
+        let program = program("acc +3\njmp +2\nacc +1\nacc -1\n");
+        let mut machine = Machine::new(&program);
+        assert_eq!(machine.step(), Ok(Status::Running));
+        assert_eq!(machine.step(), Ok(Status::Running));
+        assert_eq!(machine.cpu().pc, 3);
+        assert_eq!(machine.step(), Ok(Status::Halted));
+        assert_eq!(machine.step(), Ok(Status::Halted));
+        assert_eq!((machine.acc(), machine.steps()), (2, 3));
+    }
+
+    #[test]
+    fn breakpoints() {
+        let program = program("acc +1\nacc +2\nacc +3\n");
+        let mut machine = Machine::new(&program);
+        machine.set_breakpoint(1);
+        machine.set_breakpoint(2);
+        assert_eq!(machine.run(), Ok(Exit::Breakpoint));
+        assert_eq!((machine.cpu().pc, machine.acc()), (1, 1));
+        assert_eq!(machine.run(), Ok(Exit::Breakpoint));
+        assert_eq!((machine.cpu().pc, machine.acc()), (2, 3));
+        machine.clear_breakpoint(2);
+        assert_eq!(machine.run(), Ok(Exit::Halted));
+        assert_eq!(machine.acc(), 6);
+    }
+
+    #[test]
+    fn jump_out_of_range() {
+        let program = program("nop +0\njmp -2\n");
+        let mut machine = Machine::new(&program);
+        assert_eq!(
+            machine.run(),
+            Err(Error::JumpOutOfRange {
+                from: 1,
+                target: -1
+            })
+        );
+        assert_eq!(machine.step(), Err(Error::Reentry { pc: -1 }));
+    }
+
+    #[test]
+    fn reentry() {
+        let program = program("acc +1\n");
+        let mut machine = Machine::new(&program);
+        assert_eq!(machine.run(), Ok(Exit::Halted));
+        assert_eq!(machine.run(), Err(Error::Reentry { pc: 1 }));
+
+        let mut machine = Machine::new(&program);
+        machine.cpu_mut().pc = 5;
+        assert_eq!(machine.run(), Err(Error::Reentry { pc: 5 }));
+    }
+
+    #[test]
+    fn step_limit() {
+        let program = program("acc +1\nacc +1\nacc +1\nacc +1\n");
+        let mut machine = Machine::new(&program);
+        machine.set_step_limit(Some(3));
+        assert_eq!(machine.run(), Err(Error::StepLimit(3)));
+        assert_eq!((machine.steps(), machine.acc()), (3, 3));
+        machine.set_step_limit(None);
+        assert_eq!(machine.run(), Ok(Exit::Halted));
+        assert_eq!(machine.acc(), 4);
+    }
+
+    #[test]
+    fn trace() {
+        let program = program("acc +4\njmp +1\nacc -1\n");
+        let mut machine = Machine::new(&program);
+        machine.record_trace();
+        machine.run().unwrap();
+        let mut out = Vec::new();
+        machine.write_trace(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "step\tpc\tinstruction\tacc\n\
+             0\t0\tacc +4\t4\n\
+             1\t1\tjmp +1\t4\n\
+             2\t2\tacc -1\t3\n"
+        );
+    }
+
+    /// An instruction set with two registers, adding one to another
+    struct Add(usize, usize);
+
+    impl Display for Add {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "add r{} r{}", self.0, self.1)
+        }
+    }
+
+    impl Instruction for Add {
+        fn opcode(&self) -> &'static str {
+            "add"
+        }
+
+        fn execute(&self, cpu: &mut Cpu) -> Result<(), Error> {
+            *cpu.register_mut(self.0) += cpu.register(self.1);
+            cpu.pc += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn other_instruction_set() {
+        let program = [Add(1, 0), Add(0, 1), Add(0, 1)];
+        let mut machine = Machine::with_registers(&program, 2);
+        *machine.cpu_mut().register_mut(ACC) = 1;
+        assert_eq!(machine.run(), Ok(Exit::Halted));
+        assert_eq!(machine.cpu().registers(), &[3, 1]);
+    }
+
+    /// An instruction set with a conditional jump, so that programs can
+    /// loop on their registers
+    enum Counter {
+        Inc(usize),
+        Dec(usize),
+        /// Jumps by the offset unless the register is zero
+        Jnz(usize, i64),
+    }
+
+    impl Display for Counter {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Counter::Inc(reg) => write!(f, "inc r{}", reg),
+                Counter::Dec(reg) => write!(f, "dec r{}", reg),
+                Counter::Jnz(reg, offset) => {
+                    write!(f, "jnz r{} {:+}", reg, offset)
+                }
+            }
+        }
+    }
+
+    impl Instruction for Counter {
+        fn opcode(&self) -> &'static str {
+            match self {
+                Counter::Inc(_) => "inc",
+                Counter::Dec(_) => "dec",
+                Counter::Jnz(..) => "jnz",
+            }
+        }
+
+        fn execute(&self, cpu: &mut Cpu) -> Result<(), Error> {
+            match *self {
+                Counter::Inc(reg) => *cpu.register_mut(reg) += 1,
+                Counter::Dec(reg) => *cpu.register_mut(reg) -= 1,
+                Counter::Jnz(reg, offset) if cpu.register(reg) != 0 => {
+                    cpu.pc += offset;
+                    return Ok(());
+                }
+                Counter::Jnz(..) => {}
+            }
+            cpu.pc += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn loop_on_registers() {
+        let program = [Counter::Inc(ACC), Counter::Dec(1), Counter::Jnz(1, -2)];
+        let mut machine = Machine::with_registers(&program, 2);
+        *machine.cpu_mut().register_mut(1) = 3;
+        assert_eq!(machine.run(), Ok(Exit::Halted));
+        assert_eq!((machine.acc(), machine.steps()), (3, 9));
+
+        // Counting down from a negative number never ends
+        let mut machine = Machine::with_registers(&program, 2);
+        *machine.cpu_mut().register_mut(1) = -1;
+        machine.set_step_limit(Some(100));
+        assert_eq!(machine.run(), Err(Error::StepLimit(100)));
+    }
+
+    #[test]
+    fn overflow() {
+        let jump = program("nop +0\njmp +9223372036854775807\n");
+        let mut machine = Machine::new(&jump);
+        assert_eq!(
+            machine.run(),
+            Err(Error::JumpOutOfRange {
+                from: 1,
+                target: i64::MAX
+            })
+        );
+
+        let add = program("acc +9223372036854775807\nacc +1\n");
+        let mut machine = Machine::new(&add);
+        let err = machine.run().unwrap_err();
+        assert_eq!(
+            err,
+            Error::Overflow {
+                pc: 1,
+                register: ACC
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "instruction at 1 overflowed the accumulator"
+        );
+    }
+}
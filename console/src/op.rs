@@ -0,0 +1,87 @@
+use crate::{Cpu, Error, Instruction, ACC};
+use common::{parse_number, ErrorKind, ParseError};
+use regex::Regex;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The instruction set of the handheld game console, as in the puzzle
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Op {
+    /// Adds the argument to the accumulator
+    Acc(i64),
+    /// Jumps by the argument, relative to this instruction
+    Jmp(i64),
+    /// Does nothing
+    Nop(i64),
+}
+
+impl Instruction for Op {
+    fn opcode(&self) -> &'static str {
+        match self {
+            Self::Acc(_) => "acc",
+            Self::Jmp(_) => "jmp",
+            Self::Nop(_) => "nop",
+        }
+    }
+
+    const PC_DETERMINES_FLOW: bool = true;
+
+    fn execute(&self, cpu: &mut Cpu) -> Result<(), Error> {
+        // The machine only executes instructions inside the program
+        let pc = cpu.pc as usize;
+        match *self {
+            Self::Acc(arg) => {
+                let acc = cpu.register_mut(ACC);
+                *acc = acc
+                    .checked_add(arg)
+                    .ok_or(Error::Overflow { pc, register: ACC })?;
+                cpu.pc += 1;
+            }
+            Self::Jmp(arg) => {
+                cpu.pc = cpu.pc.checked_add(arg).ok_or_else(|| {
+                    Error::JumpOutOfRange {
+                        from: pc,
+                        target: cpu.pc.saturating_add(arg),
+                    }
+                })?;
+            }
+            Self::Nop(_) => cpu.pc += 1,
+        }
+        Ok(())
+    }
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (Self::Acc(arg) | Self::Jmp(arg) | Self::Nop(arg)) = self;
+        write!(f, "{} {:+}", self.opcode(), arg)
+    }
+}
+
+impl FromStr for Op {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref REGEX: Regex =
+                Regex::new(r"^(?P<op>\w+) (?P<arg>[\+\-]\d+)$").unwrap();
+        }
+        let captures = REGEX.captures(s).ok_or_else(|| {
+            ParseError::new(ErrorKind::InvalidSyntax("instruction"))
+                .with_token(s)
+        })?;
+        let arg_capture = captures.name("arg").unwrap();
+        let arg = parse_number(arg_capture.as_str(), "argument")
+            .map_err(|err| err.with_column(arg_capture.start() + 1))?;
+        match captures.name("op").unwrap().as_str() {
+            "acc" => Ok(Self::Acc(arg)),
+            "jmp" => Ok(Self::Jmp(arg)),
+            "nop" => Ok(Self::Nop(arg)),
+            op => Err(ParseError::new(ErrorKind::InvalidValue(
+                "unknown operation",
+            ))
+            .with_token(op)
+            .with_column(1)),
+        }
+    }
+}
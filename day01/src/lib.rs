@@ -1,3 +1,4 @@
+use common::{parse_lines_with, parse_number, Error, ParseError, Solution};
 use std::collections::HashSet;
 
 pub fn part1(input: &[i32]) -> Option<i32> {
@@ -23,3 +24,28 @@ pub fn part2(input: &[i32]) -> Option<i32> {
         })
     })
 }
+
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    parse_lines_with(input, |line| parse_number(line, "expense entry"))
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        part1(input).ok_or(Error::NotFound)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        part2(input).ok_or(Error::NotFound)
+    }
+}
@@ -0,0 +1 @@
+common::sample_tests!(day01::Day01);
@@ -1,3 +1,6 @@
+use common::{
+    parse_lines, parse_number, Error, ErrorKind, ParseError, Solution,
+};
 use regex::Regex;
 use std::str::FromStr;
 
@@ -48,32 +51,35 @@ impl PasswordEntry {
 }
 
 impl FromStr for PasswordRule {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let captures = Regex::new(r"^(\d+)-(\d+)\s(?P<ch>.)$")
             .unwrap()
             .captures(s)
-            .ok_or_else(|| "Invalid password rule")?;
+            .ok_or_else(|| {
+                ParseError::new(ErrorKind::InvalidSyntax("password rule"))
+                    .with_token(s)
+            })?;
 
         let numbers = captures
             .iter()
             .skip(1)
             .take(2)
             .map(|capture| {
-                capture
-                    .unwrap()
-                    .as_str()
-                    .parse()
-                    .map_err(|err| format!("Invalid number in rule: {}", err))
+                let capture = capture.unwrap();
+                parse_number(capture.as_str(), "number in rule")
                     .and_then(|number| {
                         if number > 0 {
                             Ok(number)
                         } else {
-                            Err("Rule numbers must be greater than zero"
-                                .to_string())
+                            Err(ParseError::new(ErrorKind::InvalidValue(
+                                "rule numbers must be greater than zero",
+                            ))
+                            .with_token(number))
                         }
                     })
+                    .map_err(|err| err.with_column(capture.start() + 1))
             })
             .collect::<Result<Vec<_>, _>>()?;
         let first_num = numbers[0];
@@ -85,7 +91,9 @@ impl FromStr for PasswordRule {
             .as_str()
             .chars()
             .next()
-            .ok_or_else(|| "Invalid rule character")?;
+            .ok_or_else(|| {
+                ParseError::new(ErrorKind::Missing("rule character"))
+            })?;
 
         Ok(Self {
             first_num,
@@ -96,16 +104,48 @@ impl FromStr for PasswordRule {
 }
 
 impl FromStr for PasswordEntry {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let captures = Regex::new(r"(?P<rule>.*):\s(?P<pwd>.*)$")
             .unwrap()
             .captures(s)
-            .ok_or_else(|| "Invalid password entry")?;
-        let rule = captures.name("rule").unwrap().as_str().parse()?;
+            .ok_or_else(|| {
+                ParseError::new(ErrorKind::InvalidSyntax("password entry"))
+                    .with_token(s)
+            })?;
+        let rule_capture = captures.name("rule").unwrap();
+        let rule =
+            rule_capture.as_str().parse().map_err(|err: ParseError| {
+                err.at_column(rule_capture.start() + 1)
+            })?;
         let password = captures.name("pwd").unwrap().as_str().to_string();
 
         Ok(Self { rule, password })
     }
 }
+
+pub fn parse_input(input: &str) -> Result<Vec<PasswordEntry>, ParseError> {
+    parse_lines(input)
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+    type Input = Vec<PasswordEntry>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part2(input))
+    }
+}
@@ -1,43 +1,7 @@
-use clap::{crate_description, App, Arg};
-use day02::*;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::process::exit;
+use clap::crate_description;
+use common::runner;
+use day02::Day02;
 
 fn main() {
-    let args = App::new(crate_description!())
-        .arg(
-            Arg::with_name("INPUT")
-                .help("File with puzzle input")
-                .required(true)
-                .index(1),
-        )
-        .get_matches();
-
-    println!(crate_description!());
-
-    let input = match read_input(args.value_of("INPUT").unwrap()) {
-        Ok(data) => data,
-        Err(err) => {
-            println!("Failed to read input: {}", err);
-            exit(2);
-        }
-    };
-
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-}
-
-fn read_input(filename: &str) -> Result<Vec<PasswordEntry>, String> {
-    let input_file = File::open(filename).map_err(|err| err.to_string())?;
-
-    BufReader::new(input_file)
-        .lines()
-        .zip(1..)
-        .map(|(line, line_num)| {
-            line.map_err(|err| (line_num, err.to_string()))
-                .and_then(|value| value.parse().map_err(|err| (line_num, err)))
-        })
-        .collect::<Result<_, _>>()
-        .map_err(|(line_num, err)| format!("Line {}: {}", line_num, err))
+    runner::main::<Day02>(crate_description!());
 }
@@ -0,0 +1 @@
+common::sample_tests!(day02::Day02);
@@ -1,32 +1,27 @@
-use std::collections::HashSet;
+use common::grid::{parse_grid, DenseGrid, Grid, Point};
+use common::{Error, ParseError, Solution};
 use std::str::FromStr;
 
-type Position = (usize, usize);
-type Slope = (usize, usize);
+type Slope = (i32, i32);
 
 pub struct TreeMap {
-    width: usize,
-    height: usize,
-    trees: HashSet<Position>,
+    trees: DenseGrid<bool>,
 }
 
 impl TreeMap {
     fn count_hits(&self, slope: &Slope) -> usize {
-        let mut position = Position::default();
+        let mut position = Point::default();
         let mut hits = 0;
 
-        while position.1 < self.height {
-            if self.trees.contains(&position) {
+        while (position.y as usize) < self.trees.height() {
+            // The map repeats to the right as many times as needed
+            if self.trees.get_wrapping(position) {
                 hits += 1;
             }
-            position = self.slide(&position, slope);
+            position = position.step(*slope);
         }
         hits
     }
-
-    fn slide(&self, position: &Position, slope: &Slope) -> Position {
-        ((position.0 + slope.0) % self.width, position.1 + slope.1)
-    }
 }
 
 pub fn part1(treemap: &TreeMap) -> usize {
@@ -41,27 +36,36 @@ pub fn part2(treemap: &TreeMap) -> usize {
 }
 
 impl FromStr for TreeMap {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let trees = s
-            .lines()
-            .enumerate()
-            .flat_map(|(y_coord, line)| {
-                line.chars().enumerate().filter(|(_, ch)| *ch != '.').map(
-                    move |(x_coord, ch)| match ch {
-                        '#' => Ok((x_coord, y_coord)),
-                        _ => Err(format!("Invalid character in map: {}", ch)),
-                    },
-                )
-            })
-            .collect::<Result<HashSet<_>, _>>()?;
-        let width = s.lines().map(|line| line.len()).max().unwrap_or(0);
-        let height = s.lines().count();
         Ok(Self {
-            width,
-            height,
-            trees,
+            trees: parse_grid(s, "map")?,
         })
     }
 }
+
+pub fn parse_input(input: &str) -> Result<TreeMap, ParseError> {
+    input.parse()
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+    type Input = TreeMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part2(input))
+    }
+}
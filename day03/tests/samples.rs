@@ -0,0 +1 @@
+common::sample_tests!(day03::Day03);
@@ -1,6 +1,7 @@
 #[macro_use]
 extern crate lazy_static;
 
+use common::{sections, tokens, Error, ErrorKind, ParseError, Solution};
 use regex::Regex;
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -73,7 +74,7 @@ impl Passport {
         }
         self.fields
             .get("hcl")
-            .map(|value| REGEX.is_match(&value))
+            .map(|value| REGEX.is_match(value))
             .unwrap_or(false)
     }
 
@@ -91,7 +92,7 @@ impl Passport {
         }
         self.fields
             .get("pid")
-            .map(|value| REGEX.is_match(&value))
+            .map(|value| REGEX.is_match(value))
             .unwrap_or(false)
     }
 }
@@ -111,22 +112,64 @@ pub fn part2(passports: &[Passport]) -> usize {
 }
 
 impl FromStr for Passport {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let fields = s
-            .split_whitespace()
-            .map(|field| {
-                let mut parts = field.split(':').map(str::to_string).take(2);
-                match (parts.next(), parts.next(), parts.next()) {
-                    (Some(key), Some(value), None) => {
-                        // TODO: check if key is valid?
-                        Ok((key, value))
+            .lines()
+            .zip(1..)
+            .flat_map(|(line, line_num)| {
+                tokens(line).map(move |(field, column)| {
+                    let mut parts =
+                        field.split(':').map(str::to_string).take(2);
+                    match (parts.next(), parts.next(), parts.next()) {
+                        (Some(key), Some(value), None) => {
+                            // TODO: check if key is valid?
+                            Ok((key, value))
+                        }
+                        _ => Err(ParseError::new(ErrorKind::InvalidSyntax(
+                            "passport field",
+                        ))
+                        .with_token(field)
+                        .at_line(line_num)
+                        .with_column(column)),
                     }
-                    _ => Err(format!("Invalid field: {}", field)),
-                }
+                })
             })
             .collect::<Result<HashMap<_, _>, _>>()?;
         Ok(Self { fields })
     }
 }
+
+pub fn parse_input(input: &str) -> Result<Vec<Passport>, ParseError> {
+    sections(input)
+        .iter()
+        .map(|section| {
+            section
+                .text
+                .parse()
+                .map_err(|err: ParseError| err.at_line(section.line_num))
+        })
+        .collect()
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+    type Input = Vec<Passport>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part2(input))
+    }
+}
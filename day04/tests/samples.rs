@@ -0,0 +1 @@
+common::sample_tests!(day04::Day04);
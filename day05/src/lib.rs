@@ -1,3 +1,4 @@
+use common::{parse_lines, Error, ErrorKind, ParseError, Solution};
 use std::convert::TryFrom;
 use std::str::FromStr;
 
@@ -41,17 +42,50 @@ fn parse_bin_str(s: &str, low_ch: char, high_ch: char) -> Option<u32> {
 }
 
 impl FromStr for Seat {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let row = s
             .get(..ROW_SPLITS)
             .and_then(|r| parse_bin_str(r, 'F', 'B'))
-            .ok_or_else(|| String::from("Invalid row"))?;
+            .ok_or_else(|| {
+                ParseError::new(ErrorKind::InvalidSyntax("row"))
+                    .with_token(s.get(..ROW_SPLITS).unwrap_or(s))
+                    .with_column(1)
+            })?;
         let col = s
             .get(ROW_SPLITS..ROW_SPLITS + COL_SPLITS)
             .and_then(|c| parse_bin_str(c, 'L', 'R'))
-            .ok_or_else(|| String::from("Invalid column"))?;
+            .ok_or_else(|| {
+                ParseError::new(ErrorKind::InvalidSyntax("column"))
+                    .with_token(s.get(ROW_SPLITS..).unwrap_or(s))
+                    .with_column(ROW_SPLITS + 1)
+            })?;
         Ok(Seat { row, col })
     }
 }
+
+pub fn parse_input(input: &str) -> Result<Vec<Seat>, ParseError> {
+    parse_lines(input)
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+    type Input = Vec<Seat>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        part1(input).ok_or(Error::NotFound)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        part2(input).ok_or(Error::NotFound)
+    }
+}
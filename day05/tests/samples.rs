@@ -0,0 +1 @@
+common::sample_tests!(day05::Day05);
@@ -1,3 +1,4 @@
+use common::{sections, Error, ParseError, Solution};
 use std::collections::HashSet;
 
 pub type Group = Vec<Answer>;
@@ -32,3 +33,37 @@ pub fn part2(groups: &[Group]) -> usize {
         })
         .sum()
 }
+
+pub fn parse_input(input: &str) -> Result<Vec<Group>, ParseError> {
+    Ok(sections(input)
+        .iter()
+        .map(|section| {
+            section
+                .text
+                .lines()
+                .map(|line| line.chars().collect())
+                .collect()
+        })
+        .collect())
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+    type Input = Vec<Group>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part2(input))
+    }
+}
@@ -0,0 +1 @@
+common::sample_tests!(day06::Day06);
@@ -1,6 +1,10 @@
 #[macro_use]
 extern crate lazy_static;
 
+use common::{
+    column_of, parse_lines, parse_number, Error, ErrorKind, ParseError,
+    Solution,
+};
 use regex::Regex;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::str::FromStr;
@@ -15,7 +19,7 @@ pub fn part1(rules: &[Rule]) -> usize {
     for rule in rules {
         for (_, inner_bag) in rule.inner.iter() {
             is_inside
-                .entry(&inner_bag)
+                .entry(inner_bag)
                 .and_modify(|set| {
                     set.insert(&rule.outer);
                 })
@@ -54,7 +58,7 @@ pub fn part2(rules: &[Rule]) -> u32 {
 }
 
 impl FromStr for Rule {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
@@ -66,8 +70,9 @@ impl FromStr for Rule {
                 Regex::new(r"^(?P<count>\d+) (?P<bag>.*) bags?$").unwrap();
         }
 
-        let outer_captures =
-            OUTER_REGEX.captures(s).ok_or_else(|| "Invalid rule")?;
+        let outer_captures = OUTER_REGEX.captures(s).ok_or_else(|| {
+            ParseError::new(ErrorKind::InvalidSyntax("rule")).with_token(s)
+        })?;
         let outer = outer_captures.name("outer").unwrap().as_str().to_string();
         let inner = outer_captures
             .name("bags")
@@ -76,18 +81,20 @@ impl FromStr for Rule {
                     .as_str()
                     .split(", ")
                     .map(|bags| {
+                        let column = column_of(s, bags);
                         let inner_captures =
                             INNER_REGEX.captures(bags).ok_or_else(|| {
-                                format!("Invalid rule contents: {}", bags)
-                            })?;
-                        let count = inner_captures
-                            .name("count")
-                            .unwrap()
-                            .as_str()
-                            .parse()
-                            .map_err(|err| {
-                                format!("Invalid number of bags: {}", err)
+                                ParseError::new(ErrorKind::InvalidSyntax(
+                                    "rule contents",
+                                ))
+                                .with_token(bags)
+                                .with_column(column)
                             })?;
+                        let count = parse_number(
+                            inner_captures.name("count").unwrap().as_str(),
+                            "number of bags",
+                        )
+                        .map_err(|err| err.with_column(column))?;
                         let bag = inner_captures
                             .name("bag")
                             .unwrap()
@@ -103,3 +110,28 @@ impl FromStr for Rule {
         Ok(Self { outer, inner })
     }
 }
+
+pub fn parse_input(input: &str) -> Result<Vec<Rule>, ParseError> {
+    parse_lines(input)
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+    type Input = Vec<Rule>;
+    type Answer1 = usize;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part2(input))
+    }
+}
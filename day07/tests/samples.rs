@@ -0,0 +1 @@
+common::sample_tests!(day07::Day07);
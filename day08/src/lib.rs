@@ -1,131 +1,44 @@
-#[macro_use]
-extern crate lazy_static;
+pub mod repair;
 
-use regex::Regex;
-use std::collections::HashSet;
-use std::convert::TryFrom;
-use std::str::FromStr;
+use common::{parse_lines, Error, ParseError, Solution};
+use console::{Exit, Machine, Op};
+use repair::repairs;
 
-type Program = [Instruction];
+type Program = [Op];
 
-#[derive(Clone)]
-pub enum Instruction {
-    Acc(i32),
-    Jmp(i32),
-    Nop(i32),
+pub fn part1(program: &Program) -> Option<i64> {
+    let mut machine = Machine::new(program);
+    match machine.run() {
+        Ok(Exit::Loop) => Some(machine.acc()),
+        _ => None,
+    }
 }
 
-enum State {
-    Error,
-    Loop(i32),
-    Completed(i32),
+pub fn part2(program: &Program) -> Option<i64> {
+    repairs(program).first().map(|repair| repair.acc)
 }
 
-struct Execution<'a> {
-    program: &'a Program,
-    prog_counter: i32,
-    accummulator: i32,
+pub fn parse_input(input: &str) -> Result<Vec<Op>, ParseError> {
+    parse_lines(input)
 }
 
-impl<'a> Execution<'a> {
-    fn new(program: &'a Program) -> Self {
-        Self {
-            program,
-            prog_counter: 0,
-            accummulator: 0,
-        }
-    }
+pub struct Day08;
 
-    fn run(&mut self) -> State {
-        if self.prog_counter != 0 {
-            return State::Error;
-        }
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+    type Input = Vec<Op>;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-        let mut visited = HashSet::new();
-        visited.insert(0);
-
-        while let Some(pg) = self.step() {
-            if !visited.insert(pg) {
-                return State::Loop(self.accummulator);
-            }
-            if pg == self.program.len() {
-                return State::Completed(self.accummulator);
-            }
-        }
-        State::Error
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    fn get_prog_counter(&self) -> Option<usize> {
-        usize::try_from(self.prog_counter).ok()
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        part1(input).ok_or(Error::NotFound)
     }
 
-    fn step(&mut self) -> Option<usize> {
-        let pg = self.get_prog_counter()?;
-        match self.program.get(pg)? {
-            Instruction::Acc(arg) => {
-                self.accummulator += arg;
-                self.prog_counter += 1;
-            }
-            Instruction::Jmp(arg) => {
-                self.prog_counter += arg;
-            }
-            Instruction::Nop(_) => {
-                self.prog_counter += 1;
-            }
-        };
-        self.get_prog_counter()
-    }
-}
-
-pub fn part1(program: &Program) -> Option<i32> {
-    match Execution::new(program).run() {
-        State::Loop(acc) => Some(acc),
-        _ => None,
-    }
-}
-
-pub fn part2(program: &Program) -> Option<i32> {
-    let mut mod_prog = program.to_vec();
-    for (pos, instruction) in program.iter().enumerate() {
-        match instruction {
-            Instruction::Jmp(arg) => mod_prog[pos] = Instruction::Nop(*arg),
-            Instruction::Nop(arg) => mod_prog[pos] = Instruction::Jmp(*arg),
-            _ => continue,
-        };
-
-        // Try the modified program
-        if let State::Completed(acc) = Execution::new(&mod_prog).run() {
-            return Some(acc);
-        }
-
-        // Restore the original instruction
-        mod_prog[pos] = instruction.clone();
-    }
-    // No modified program ever completed
-    None
-}
-
-impl FromStr for Instruction {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static! {
-            static ref REGEX: Regex =
-                Regex::new(r"^(?P<op>\w+) (?P<arg>[\+\-]\d+)$").unwrap();
-        }
-        let captures =
-            REGEX.captures(s).ok_or_else(|| "Invalid instruction")?;
-        let arg = captures
-            .name("arg")
-            .unwrap()
-            .as_str()
-            .parse()
-            .map_err(|err| format!("Invalid argument: {}", err))?;
-        match captures.name("op").unwrap().as_str() {
-            "acc" => Ok(Self::Acc(arg)),
-            "jmp" => Ok(Self::Jmp(arg)),
-            "nop" => Ok(Self::Nop(arg)),
-            op => Err(format!("Unknown operation: {}", op)),
-        }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        part2(input).ok_or(Error::NotFound)
     }
 }
@@ -1,50 +1,282 @@
-use clap::{crate_description, App, Arg};
-use day08::*;
+use clap::{crate_description, Arg, ArgMatches};
+use common::runner::{self, read_input};
+use console::asm::{assemble, disassemble};
+use console::flow::{Block, Flow};
+use console::{Exit, Machine, Op};
+use day08::repair::repairs;
+use day08::{parse_input, Day08};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufWriter, Write};
 use std::process::exit;
 
 fn main() {
-    let args = App::new(crate_description!())
+    let args = runner::app(crate_description!())
         .arg(
-            Arg::with_name("INPUT")
-                .help("File with puzzle input")
-                .required(true)
-                .index(1),
+            Arg::with_name("trace")
+                .long("trace")
+                .value_name("FILE")
+                .conflicts_with_all(&["format", "part", "check"])
+                .help(
+                    "Runs the program as is and writes every instruction \
+                     executed to a file",
+                ),
+        )
+        .arg(
+            Arg::with_name("break")
+                .long("break")
+                .value_name("PC")
+                .multiple(true)
+                .number_of_values(1)
+                .validator(|value| {
+                    value
+                        .parse::<usize>()
+                        .map(|_| ())
+                        .map_err(|_| "must be a number".to_string())
+                })
+                .conflicts_with_all(&["format", "part", "check"])
+                .help(
+                    "Runs the program as is, printing the accumulator \
+                     whenever it reaches the instruction at this position",
+                ),
+        )
+        .arg(
+            Arg::with_name("step-limit")
+                .long("step-limit")
+                .value_name("N")
+                .validator(|value| {
+                    value
+                        .parse::<usize>()
+                        .map(|_| ())
+                        .map_err(|_| "must be a number".to_string())
+                })
+                .conflicts_with_all(&["format", "part", "check"])
+                .help(
+                    "Runs the program as is, failing once it executed this \
+                     many instructions",
+                ),
+        )
+        .arg(
+            Arg::with_name("repairs")
+                .long("repairs")
+                .conflicts_with_all(&[
+                    "format",
+                    "part",
+                    "check",
+                    "trace",
+                    "break",
+                    "step-limit",
+                ])
+                .help(
+                    "Lists every instruction that can be flipped to make \
+                     the program terminate",
+                ),
+        )
+        .arg(
+            Arg::with_name("show")
+                .long("show")
+                .value_name("WHAT")
+                .possible_values(&["disassembly", "dot", "analysis"])
+                .conflicts_with_all(&[
+                    "format",
+                    "part",
+                    "check",
+                    "trace",
+                    "break",
+                    "step-limit",
+                    "repairs",
+                ])
+                .help(
+                    "Prints the program as labelled basic blocks, its \
+                     control flow graph in the DOT language, or the code \
+                     that never runs and the loops that never end",
+                ),
+        )
+        .arg(
+            Arg::with_name("assemble")
+                .long("assemble")
+                .conflicts_with_all(&[
+                    "format",
+                    "part",
+                    "check",
+                    "trace",
+                    "break",
+                    "step-limit",
+                    "repairs",
+                    "show",
+                ])
+                .help(
+                    "Reads programs with labels and prints them in the \
+                     puzzle's form",
+                ),
         )
         .get_matches();
 
-    println!(crate_description!());
+    if args.is_present("assemble") {
+        assemble_programs(&args);
+    } else if let Some(what) = args.value_of("show") {
+        show(&args, what);
+    } else if args.is_present("repairs") {
+        list_repairs(&args);
+    } else if ["trace", "break", "step-limit"]
+        .iter()
+        .any(|&name| args.is_present(name))
+    {
+        debug(&args);
+    } else {
+        runner::run::<Day08>(&args);
+    }
+}
+
+fn read_program(filename: &str) -> Vec<Op> {
+    runner::parse_file(filename, parse_input).unwrap_or_else(|err| {
+        println!("{}: {}", filename, err);
+        exit(2);
+    })
+}
 
-    let input = match read_input(args.value_of("INPUT").unwrap()) {
-        Ok(data) => data,
-        Err(err) => {
-            println!("Failed to read input: {}", err);
-            exit(2);
+/// Prints the assembled programs of every input.
+fn assemble_programs(args: &ArgMatches) {
+    for filename in runner::input_args(args) {
+        let program = read_input(filename)
+            .and_then(|input| assemble(&input).map_err(|err| err.to_string()));
+        match program {
+            Ok(program) => {
+                for op in program {
+                    println!("{}", op);
+                }
+            }
+            Err(err) => {
+                println!("{}: failed to assemble: {}", filename, err);
+                exit(2);
+            }
         }
-    };
+    }
+}
+
+/// Prints the requested view of the program of every input.
+fn show(args: &ArgMatches, what: &str) {
+    for filename in runner::input_args(args) {
+        let program = read_program(filename);
+        let flow = Flow::new(&program);
+        match what {
+            "disassembly" => print!("{}", disassemble(&program)),
+            "dot" => print!("{}", flow.to_dot(&program)),
+            _ => print_analysis(filename, &flow),
+        }
+    }
+}
 
-    match part1(&input) {
-        Some(result) => println!("Part 1: {}", result),
-        None => println!("Part 1: not found"),
-    };
+fn print_analysis(filename: &str, flow: &Flow) {
+    let range = |block: &Block| format!("{}-{}", block.start, block.end - 1);
+    let unreachable: Vec<_> =
+        flow.unreachable().into_iter().map(range).collect();
+    if unreachable.is_empty() {
+        println!("{}: no unreachable code", filename);
+    } else {
+        println!(
+            "{}: unreachable code at {}",
+            filename,
+            unreachable.join(", ")
+        );
+    }
+    for blocks in flow.infinite_loops() {
+        let blocks: Vec<_> = blocks.into_iter().map(range).collect();
+        println!("{}: infinite loop through {}", filename, blocks.join(", "));
+    }
+    if flow.terminates() {
+        println!("{}: terminates", filename);
+    } else {
+        println!("{}: does not terminate", filename);
+    }
+}
 
-    match part2(&input) {
-        Some(result) => println!("Part 2: {}", result),
-        None => println!("Part 2: not found"),
-    };
+/// Prints the possible repairs of the program of every input.
+fn list_repairs(args: &ArgMatches) {
+    for filename in runner::input_args(args) {
+        let repairs = repairs(&read_program(filename));
+        if repairs.is_empty() {
+            println!("{}: no repairs", filename);
+        }
+        for repair in repairs {
+            println!(
+                "{}: flip {} at {}, accumulator {}",
+                filename, repair.original, repair.position, repair.acc
+            );
+        }
+    }
 }
 
-fn read_input(filename: &str) -> Result<Vec<Instruction>, String> {
-    let input_file = File::open(filename).map_err(|err| err.to_string())?;
-
-    BufReader::new(input_file)
-        .lines()
-        .zip(1..)
-        .map(|(line, line_num)| {
-            line.map_err(|err| (line_num, err.to_string()))
-                .and_then(|value| value.parse().map_err(|err| (line_num, err)))
-        })
-        .collect::<Result<_, _>>()
-        .map_err(|(line_num, err)| format!("Line {}: {}", line_num, err))
+/// Runs the program of every input as requested on the command line.
+/// Exits with an error unless every program halted.
+fn debug(args: &ArgMatches) {
+    let inputs = runner::input_args(args);
+    if args.is_present("trace") && inputs.len() > 1 {
+        println!("Only one input can be traced at a time");
+        exit(2);
+    }
+    let mut failed = false;
+    for filename in inputs {
+        let program = read_program(filename);
+        let mut machine = Machine::new(&program);
+        if args.is_present("trace") {
+            machine.record_trace();
+        }
+        machine.set_step_limit(
+            args.value_of("step-limit")
+                .map(|limit| limit.parse().unwrap()),
+        );
+        for pc in args.values_of("break").into_iter().flatten() {
+            machine.set_breakpoint(pc.parse().unwrap());
+        }
+        let result = loop {
+            match machine.run() {
+                Ok(Exit::Breakpoint) => println!(
+                    "{}: breakpoint at {} after {} steps, accumulator {}",
+                    filename,
+                    machine.cpu().pc,
+                    machine.steps(),
+                    machine.acc()
+                ),
+                result => break result,
+            }
+        };
+        failed |= result != Ok(Exit::Halted);
+        match result {
+            Ok(Exit::Loop) => println!(
+                "{}: loop at {} after {} steps, accumulator {}",
+                filename,
+                machine.cpu().pc,
+                machine.steps(),
+                machine.acc()
+            ),
+            Ok(_) => println!(
+                "{}: halted after {} steps, accumulator {}",
+                filename,
+                machine.steps(),
+                machine.acc()
+            ),
+            Err(err) => println!(
+                "{}: {} after {} steps, accumulator {}",
+                filename,
+                err,
+                machine.steps(),
+                machine.acc()
+            ),
+        }
+
+        if let Some(trace_file) = args.value_of("trace") {
+            let written = File::create(trace_file).and_then(|file| {
+                let mut out = BufWriter::new(file);
+                machine.write_trace(&mut out)?;
+                out.flush()
+            });
+            if let Err(err) = written {
+                println!("Failed to write trace: {}", err);
+                exit(2);
+            }
+        }
+    }
+    if failed {
+        exit(1);
+    }
 }
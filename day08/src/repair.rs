@@ -0,0 +1,119 @@
+//! Finding which single `jmp` or `nop` to flip to make a looping program
+//! terminate, without running every modified program.
+//!
+//! A flip can only matter if the original program executes the flipped
+//! instruction, and it fixes the program if the flipped instruction leads to
+//! an instruction from which the original program terminates. So it's
+//! enough to find the instructions that terminate, going back from the end
+//! of the program once, and then to run the original program once.
+
+use console::flow::{target, Target};
+use console::Op;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+
+/// A single flip that makes the program terminate
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Repair {
+    /// Position of the flipped instruction
+    pub position: usize,
+    /// The instruction before it was flipped
+    pub original: Op,
+    /// The accumulator when the repaired program terminates
+    pub acc: i64,
+}
+
+/// Position of the next instruction, or `None` if it's outside the program.
+/// The end of the program, just past the last instruction, is `len`.
+fn successor(op: Op, position: usize, len: usize) -> Option<usize> {
+    match target(op, position, len) {
+        Target::Instruction(next) => Some(next),
+        Target::End => Some(len),
+        Target::Outside(_) => None,
+    }
+}
+
+fn flipped(op: Op) -> Option<Op> {
+    match op {
+        Op::Jmp(arg) => Some(Op::Nop(arg)),
+        Op::Nop(arg) => Some(Op::Jmp(arg)),
+        Op::Acc(_) => None,
+    }
+}
+
+/// For each position, the amount added to the accumulator from there until
+/// the end of the program, or `None` if the program doesn't terminate when
+/// started there. The last entry is for the end of the program itself.
+/// Sums are wide enough that they never overflow.
+fn acc_to_end(program: &[Op]) -> Vec<Option<i128>> {
+    let len = program.len();
+    let mut predecessors = vec![Vec::new(); len + 1];
+    for (position, &op) in program.iter().enumerate() {
+        if let Some(next) = successor(op, position, len) {
+            predecessors[next].push(position);
+        }
+    }
+
+    // Every instruction is reached after its successor, so the rest of the
+    // way to the end is always known
+    let mut acc_to_end = vec![None; len + 1];
+    acc_to_end[len] = Some(0);
+    let mut queue: VecDeque<_> = vec![len].into();
+    while let Some(next) = queue.pop_front() {
+        let rest = acc_to_end[next].unwrap();
+        for &position in predecessors[next].iter() {
+            let added = match program[position] {
+                Op::Acc(arg) => arg as i128,
+                _ => 0,
+            };
+            acc_to_end[position] = Some(added + rest);
+            queue.push_back(position);
+        }
+    }
+    acc_to_end
+}
+
+/// Every single flip of a `jmp` into a `nop` or the other way round that
+/// makes the program terminate, by position. Empty if the program already
+/// terminates. Flips that leave a final accumulator too large for the console
+/// are left out.
+pub fn repairs(program: &[Op]) -> Vec<Repair> {
+    let len = program.len();
+    let acc_to_end = acc_to_end(program);
+    let mut executed = vec![false; len];
+    let mut repairs = Vec::new();
+    let mut position = 0;
+    let mut acc = 0;
+
+    while position < len && !executed[position] {
+        executed[position] = true;
+        let op = program[position];
+        if let Some(flipped) = flipped(op) {
+            let acc = successor(flipped, position, len)
+                .and_then(|next| acc_to_end[next])
+                .and_then(|rest| i64::try_from(acc + rest).ok());
+            if let Some(acc) = acc {
+                repairs.push(Repair {
+                    position,
+                    original: op,
+                    acc,
+                });
+            }
+        }
+        if let Op::Acc(arg) = op {
+            acc += arg as i128;
+        }
+        match successor(op, position, len) {
+            Some(next) => position = next,
+            // Leaves the program, which a flip on the way may avoid
+            None => break,
+        }
+    }
+
+    if position == len {
+        // Already terminates
+        return Vec::new();
+    }
+    repairs.sort_unstable_by_key(|repair| repair.position);
+    repairs
+}
@@ -0,0 +1,79 @@
+use console::Op;
+use day08::parse_input;
+use day08::repair::{repairs, Repair};
+use day08::Day08;
+use std::fs::read_to_string;
+use std::path::Path;
+
+common::sample_tests!(Day08);
+
+fn program(text: &str) -> Vec<Op> {
+    parse_input(text).unwrap()
+}
+
+#[test]
+fn repairs_of_example() {
+    let input = read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("samples/example.txt"),
+    )
+    .unwrap();
+    let repair = Repair {
+        position: 7,
+        original: Op::Jmp(-4),
+        acc: 8,
+    };
+    assert_eq!(repairs(&program(&input)), vec![repair]);
+}
+
+#[test]
+fn several_repairs() {
+    let program = program(concat!(
+        "acc +1\n",
+        "jmp +3\n",
+        "acc +100\n",
+        "jmp +3\n",
+        "nop +0\n",
+        "jmp -1\n",
+    ));
+    let first = Repair {
+        position: 1,
+        original: Op::Jmp(3),
+        acc: 101,
+    };
+    let second = Repair {
+        position: 5,
+        original: Op::Jmp(-1),
+        acc: 1,
+    };
+    assert_eq!(repairs(&program), vec![first, second]);
+}
+
+#[test]
+fn nothing_to_repair() {
+    assert!(repairs(&program("acc +1\nnop +5\n")).is_empty());
+}
+
+#[test]
+fn repair_jump_out_of_program() {
+    let repair = Repair {
+        position: 1,
+        original: Op::Jmp(-5),
+        acc: 1,
+    };
+    assert_eq!(repairs(&program("acc +1\njmp -5\n")), vec![repair]);
+}
+
+#[test]
+fn repair_overflowing_accumulator() {
+    // Flipping the jump ends the program with an accumulator too large for
+    // the console
+    let overflowing = program("acc +9223372036854775807\nacc +1\njmp -2\n");
+    assert!(repairs(&overflowing).is_empty());
+    let fitting = program("acc +9223372036854775807\nacc -1\njmp -2\n");
+    let repair = Repair {
+        position: 2,
+        original: Op::Jmp(-2),
+        acc: i64::MAX - 1,
+    };
+    assert_eq!(repairs(&fitting), vec![repair]);
+}
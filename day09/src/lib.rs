@@ -1,10 +1,14 @@
+use common::{parse_lines_with, parse_number, Error, ParseError, Solution};
+
+const PREAMBLE_LEN: usize = 25;
+
 pub fn part1(input: &[i64], preamble_len: usize) -> Option<i64> {
     input.windows(preamble_len + 1).find_map(|window| {
         let next_number = window[preamble_len];
         let has_sum = window.iter().enumerate().take(preamble_len - 1).any(
             |(index, number)| {
                 let target = next_number - number;
-                window[index + 1..preamble_len].iter().any(|&n| n == target)
+                window[index + 1..preamble_len].contains(&target)
             },
         );
 
@@ -41,6 +45,32 @@ pub fn part2(input: &[i64], target: i64) -> Option<i64> {
         })
 }
 
+pub fn parse_input(input: &str) -> Result<Vec<i64>, ParseError> {
+    parse_lines_with(input, |line| parse_number(line, "number"))
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        part1(input, PREAMBLE_LEN).ok_or(Error::NotFound)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        let target = part1(input, PREAMBLE_LEN).ok_or(Error::NotFound)?;
+        part2(input, target).ok_or(Error::NotFound)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
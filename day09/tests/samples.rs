@@ -0,0 +1 @@
+common::sample_tests!(day09::Day09);
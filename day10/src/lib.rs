@@ -1,3 +1,4 @@
+use common::{parse_lines_with, parse_number, Error, ParseError, Solution};
 use std::collections::HashMap;
 use std::convert::TryFrom;
 
@@ -36,7 +37,7 @@ pub fn part2(adapters: &[i32]) -> Option<u64> {
 
     let len = 1 + adapters.len();
     for (index, adapter) in sorted_adapters.iter().enumerate().take(len) {
-        let count = path_count.remove(&adapter)?;
+        let count = path_count.remove(adapter)?;
         for next_adapter in sorted_adapters[index + 1..]
             .iter()
             .take_while(|&next| next - adapter <= 3)
@@ -50,3 +51,28 @@ pub fn part2(adapters: &[i32]) -> Option<u64> {
 
     path_count.get(&target).copied()
 }
+
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    parse_lines_with(input, |line| parse_number(line, "joltage rating"))
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        part1(input).ok_or(Error::NotFound)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        part2(input).ok_or(Error::NotFound)
+    }
+}
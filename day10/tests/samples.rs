@@ -0,0 +1 @@
+common::sample_tests!(day10::Day10);
@@ -1,62 +1,191 @@
-use std::collections::{BTreeSet, HashMap};
+use common::automaton::{Graph, Rule};
+use common::grid::{parse_grid, Cell, DenseGrid, Grid, Neighbourhood, Point};
+use common::{Error, ErrorKind, ParseError, Solution};
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
-pub mod part1;
-pub mod part2;
+pub mod seat_map;
 
-#[derive(Clone, Copy, PartialEq)]
+pub use seat_map::{seat_graph, seat_rule, Generation, Generations, SeatMap};
+
+pub fn part1(seating_area: &SeatingArea) -> usize {
+    simulate(seating_area, &Policy::PART1)
+}
+
+pub fn part2(seating_area: &SeatingArea) -> usize {
+    simulate(seating_area, &Policy::PART2)
+}
+
+/// Lets people sit down and leave following the policy until no seat
+/// changes, and returns the number of occupied seats.
+pub fn simulate(seating_area: &SeatingArea, policy: &Policy) -> usize {
+    let mut seat_map = SeatMap::new(seating_area, policy);
+    seat_map.run_until_stable();
+    seat_map.count_occupied()
+}
+
+/// Which seats people pay attention to
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Visibility {
+    /// The seats in the 8 positions around theirs
+    Adjacent,
+    /// The first seat they can see in each of the 8 directions, ignoring
+    /// seats more than `radius` positions away if given
+    LineOfSight { radius: Option<usize> },
+}
+
+impl Visibility {
+    /// Positions of the seats watched by people at the given position.
+    fn watched_seats(
+        self,
+        seating_area: &SeatingArea,
+        pos: Point,
+    ) -> Vec<Point> {
+        match self {
+            Self::Adjacent => pos.neighbours(Neighbourhood::All).collect(),
+            Self::LineOfSight { radius } => Neighbourhood::All
+                .directions()
+                .iter()
+                .filter_map(|&direction| {
+                    seating_area.grid.line_of_sight(pos, direction, |area| {
+                        area == Area::Floor
+                    })
+                })
+                .filter(|seat| {
+                    let distance =
+                        (seat.x - pos.x).abs().max((seat.y - pos.y).abs());
+                    radius.is_none_or(|radius| distance as usize <= radius)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// How people choose where to sit
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Policy {
+    pub visibility: Visibility,
+    /// Number of occupied watched seats that makes people leave their seat
+    pub tolerance: usize,
+}
+
+impl Policy {
+    /// People watch adjacent seats and leave when 4 are occupied
+    pub const PART1: Self = Self {
+        visibility: Visibility::Adjacent,
+        tolerance: 4,
+    };
+
+    /// People watch the first seats they can see and leave when 5 are
+    /// occupied
+    pub const PART2: Self = Self {
+        visibility: Visibility::LineOfSight { radius: None },
+        tolerance: 5,
+    };
+
+    /// The rule of the seating automaton, see [`seat_rule`]
+    pub fn rule(&self) -> Rule {
+        seat_rule(self.tolerance)
+    }
+
+    /// The seats as the topology of the seating automaton, see
+    /// [`seat_graph`]
+    pub fn topology(&self, seating_area: &SeatingArea) -> Graph<Point> {
+        seat_graph(seating_area, |pos| {
+            self.visibility.watched_seats(seating_area, pos)
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 enum Area {
+    #[default]
     Floor,
     Empty,
     Occupied,
 }
 
-#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
-struct Position {
-    row: i32,
-    col: i32,
+impl Cell for Area {
+    fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            '.' => Some(Self::Floor),
+            'L' => Some(Self::Empty),
+            '#' => Some(Self::Occupied),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Self::Floor => '.',
+            Self::Empty => 'L',
+            Self::Occupied => '#',
+        }
+    }
 }
 
-impl Position {
-    fn new(row: i32, col: i32) -> Self {
-        Self { row, col }
+#[derive(Debug)]
+pub struct SeatingArea {
+    grid: DenseGrid<Area>,
+}
+
+impl SeatingArea {
+    fn seats(&self) -> impl Iterator<Item = (Point, Area)> + '_ {
+        self.grid
+            .points()
+            .map(move |pos| (pos, self.grid.cell(pos)))
+            .filter(|&(_, area)| area != Area::Floor)
     }
+}
 
-    fn adjacents(&self) -> impl Iterator<Item = Position> + '_ {
-        all_directions().map(move |(row_delta, col_delta)| {
-            Position::new(self.row + row_delta, self.col + col_delta)
+impl FromStr for SeatingArea {
+    type Err = ParseError;
+
+    /// Parses the seating area, one row per line. Every row must have the
+    /// same length, as a short row most likely means a truncated input.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let width = s.lines().next().map_or(0, |line| line.chars().count());
+        for (line, line_num) in s.lines().zip(1..) {
+            if line.chars().count() != width {
+                return Err(ParseError::new(ErrorKind::InvalidValue(
+                    "all rows must have the same length",
+                ))
+                .at_line(line_num));
+            }
+        }
+        Ok(Self {
+            grid: parse_grid(s, "seating area")?,
         })
     }
 }
 
-fn all_directions() -> impl Iterator<Item = (i32, i32)> {
-    (-1_i32..=1_i32)
-        .flat_map(|row_delta: i32| {
-            (-1_i32..=1_i32).map(move |col_delta: i32| (row_delta, col_delta))
-        })
-        .filter(|(row_delta, col_delta)| *row_delta != 0 || *col_delta != 0)
+impl Display for SeatingArea {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
 }
 
-pub struct SeatingArea {
-    grid: HashMap<Position, Area>,
+pub fn parse_input(input: &str) -> Result<SeatingArea, ParseError> {
+    input.parse()
 }
 
-impl FromStr for SeatingArea {
-    type Err = String;
+pub struct Day11;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let grid = s
-            .lines()
-            .zip(0_i32..)
-            .flat_map(|(line, col)| {
-                line.chars().zip(0_i32..).map(move |(ch, row)| match ch {
-                    '.' => Ok((Position::new(row, col), Area::Floor)),
-                    'L' => Ok((Position::new(row, col), Area::Empty)),
-                    '#' => Ok((Position::new(row, col), Area::Occupied)),
-                    _ => Err(format!("Invalid character in map: {}", ch)),
-                })
-            })
-            .collect::<Result<HashMap<_, _>, _>>()?;
-        Ok(Self { grid })
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Input = SeatingArea;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part2(input))
     }
 }
@@ -1,36 +1,118 @@
-use clap::{crate_description, App, Arg};
-use day11::*;
-use part1::*;
-use part2::*;
-use std::fs::read_to_string;
+use clap::{crate_description, Arg, ArgMatches, Error, ErrorKind};
+use common::runner;
+use day11::{parse_input, simulate, Day11, Policy, SeatMap, Visibility};
 use std::process::exit;
 
+fn number_validator(value: String) -> Result<(), String> {
+    value
+        .parse::<usize>()
+        .map(|_| ())
+        .map_err(|_| "must be a number".to_string())
+}
+
 fn main() {
-    let args = App::new(crate_description!())
+    let args = runner::app(crate_description!())
+        .arg(
+            Arg::with_name("visibility")
+                .long("visibility")
+                .value_name("SEATS")
+                .possible_values(&["adjacent", "sight"])
+                .conflicts_with_all(&["format", "part", "check"])
+                .help(
+                    "Seats that people pay attention to: the adjacent ones, \
+                     or the first ones in sight in each direction",
+                ),
+        )
+        .arg(
+            Arg::with_name("radius")
+                .long("radius")
+                .value_name("N")
+                .validator(number_validator)
+                .requires("visibility")
+                .help("Ignores seats in sight further than this"),
+        )
+        .arg(
+            Arg::with_name("tolerance")
+                .long("tolerance")
+                .value_name("N")
+                .validator(number_validator)
+                .requires("visibility")
+                .help(
+                    "Number of occupied seats in view that makes people \
+                     leave, 4 for adjacent seats and 5 for seats in sight \
+                     by default",
+                ),
+        )
         .arg(
-            Arg::with_name("INPUT")
-                .help("File with puzzle input")
-                .required(true)
-                .index(1),
+            Arg::with_name("trace")
+                .long("trace")
+                .conflicts_with_all(&["format", "part", "check"])
+                .help(
+                    "Prints the seating area at every generation, with the \
+                     number of seats that changed",
+                ),
         )
         .get_matches();
 
-    println!(crate_description!());
+    if args.is_present("visibility") || args.is_present("trace") {
+        simulate_policy(&args);
+    } else {
+        runner::run::<Day11>(&args);
+    }
+}
 
-    let input = match read_input(args.value_of("INPUT").unwrap()) {
-        Ok(data) => data,
-        Err(err) => {
-            println!("Failed to read input: {}", err);
-            exit(2);
-        }
+/// Runs the simulation with the policy given on the command line for every
+/// input.
+fn simulate_policy(args: &ArgMatches) {
+    let radius = args
+        .value_of("radius")
+        .map(|radius| radius.parse().unwrap());
+    let default = match args.value_of("visibility") {
+        Some("sight") => Policy::PART2,
+        _ => Policy::PART1,
+    };
+    let visibility = match default.visibility {
+        Visibility::Adjacent if radius.is_some() => Error::with_description(
+            "--radius only applies to seats in sight",
+            ErrorKind::ArgumentConflict,
+        )
+        .exit(),
+        Visibility::Adjacent => Visibility::Adjacent,
+        Visibility::LineOfSight { .. } => Visibility::LineOfSight { radius },
+    };
+    let tolerance = args
+        .value_of("tolerance")
+        .map_or(default.tolerance, |tolerance| tolerance.parse().unwrap());
+    let policy = Policy {
+        visibility,
+        tolerance,
     };
 
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-}
-
-fn read_input(filename: &str) -> Result<SeatingArea, String> {
-    read_to_string(filename)
-        .map_err(|err| err.to_string())
-        .and_then(|s| s.parse())
+    for filename in runner::input_args(args) {
+        let seating_area = runner::parse_file(filename, parse_input);
+        match seating_area {
+            Ok(seating_area) if args.is_present("trace") => {
+                let seat_map = SeatMap::new(&seating_area, &policy);
+                let mut occupied = 0;
+                for generation in seat_map.generations() {
+                    println!(
+                        "{}: generation {}, {} seats changed:\n",
+                        filename, generation.index, generation.changed
+                    );
+                    println!("{}", generation.seat_map.render());
+                    occupied = generation.seat_map.count_occupied();
+                }
+                println!("{}: {} occupied seats", filename, occupied);
+            }
+            Ok(seating_area) => println!(
+                "{}: {} occupied seats",
+                filename,
+                simulate(&seating_area, &policy)
+            ),
+            Err(err) => {
+                println!("{}: {}", filename, err);
+                exit(2);
+            }
+        }
+    }
 }
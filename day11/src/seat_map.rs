@@ -0,0 +1,188 @@
+use super::*;
+use common::automaton::{DenseAutomaton, Graph, Rule};
+use std::collections::HashMap;
+
+/// The rule of the seating automaton, with occupied seats as active cells:
+/// people sit in empty seats when no seat they watch is occupied, and leave
+/// when `tolerance` of them or more are.
+pub fn seat_rule(tolerance: usize) -> Rule {
+    Rule::new(&[0], &(0..tolerance).collect::<Vec<_>>())
+}
+
+/// The seats of a seating area as the topology of an automaton, from the
+/// positions of the seats that people at each position pay attention to.
+/// The neighbours of a seat are the seats whose people watch it, which
+/// count it when it's occupied. Positions without a seat are ignored.
+pub fn seat_graph<F, I>(
+    seating_area: &SeatingArea,
+    watched_seats: F,
+) -> Graph<Point>
+where
+    F: Fn(Point) -> I,
+    I: IntoIterator<Item = Point>,
+{
+    let grid = &seating_area.grid;
+    let is_seat = |pos| grid.get(pos).is_some_and(|area| area != Area::Floor);
+    let mut watchers: HashMap<_, _> = seating_area
+        .seats()
+        .map(|(pos, _)| (pos, Vec::new()))
+        .collect();
+    for (pos, _) in seating_area.seats() {
+        for other in watched_seats(pos)
+            .into_iter()
+            .filter(|&other| is_seat(other))
+        {
+            watchers.get_mut(&other).unwrap().push(pos);
+        }
+    }
+    Graph::new(watchers)
+}
+
+/// The state of every seat of a seating area, simulated by a dense automaton
+/// on the seats, as floor never changes.
+///
+/// Cloning a map is cheap, as only the occupancy of the seats is copied.
+#[derive(Clone)]
+pub struct SeatMap {
+    automaton: DenseAutomaton<Point>,
+    width: usize,
+    height: usize,
+}
+
+impl SeatMap {
+    /// Builds the map for people following the policy.
+    pub fn new(seating_area: &SeatingArea, policy: &Policy) -> Self {
+        Self::with_topology(
+            seating_area,
+            &policy.topology(seating_area),
+            policy.rule(),
+        )
+    }
+
+    /// Builds the map from the positions of the seats that people at each
+    /// position pay attention to. Positions without a seat are ignored.
+    pub fn build<F, I>(
+        seating_area: &SeatingArea,
+        tolerance: usize,
+        watched_seats: F,
+    ) -> Self
+    where
+        F: Fn(Point) -> I,
+        I: IntoIterator<Item = Point>,
+    {
+        Self::with_topology(
+            seating_area,
+            &seat_graph(seating_area, watched_seats),
+            seat_rule(tolerance),
+        )
+    }
+
+    fn with_topology(
+        seating_area: &SeatingArea,
+        topology: &Graph<Point>,
+        rule: Rule,
+    ) -> Self {
+        let occupied = seating_area
+            .seats()
+            .filter(|&(_, area)| area == Area::Occupied)
+            .map(|(pos, _)| pos);
+        Self {
+            automaton: DenseAutomaton::new(topology, rule, occupied),
+            width: seating_area.grid.width(),
+            height: seating_area.grid.height(),
+        }
+    }
+
+    pub fn count_occupied(&self) -> usize {
+        self.automaton.count_active()
+    }
+
+    /// Whether the seat at the position is occupied, with `x` the column and
+    /// `y` the row. Floor and positions outside the area never are.
+    pub fn is_occupied(&self, pos: Point) -> bool {
+        self.automaton.is_active(&pos)
+    }
+
+    /// Lets everyone sit down or leave at once, returning the number of
+    /// seats that changed.
+    pub fn step(&mut self) -> usize {
+        self.automaton.step()
+    }
+
+    /// The seating area in this state.
+    pub fn seating_area(&self) -> SeatingArea {
+        let mut grid = DenseGrid::new(self.width, self.height);
+        for (&pos, occupied) in self.automaton.states() {
+            let area = if occupied {
+                Area::Occupied
+            } else {
+                Area::Empty
+            };
+            grid.set(pos, area);
+        }
+        SeatingArea { grid }
+    }
+
+    /// Writes the seating area in this state as in the puzzle input, with
+    /// `L` for empty seats, `#` for occupied ones and `.` for floor.
+    pub fn render(&self) -> String {
+        self.seating_area().to_string()
+    }
+
+    /// Every state from this one until no seat changes anymore.
+    pub fn generations(self) -> Generations {
+        Generations {
+            seat_map: self,
+            index: 0,
+            done: false,
+        }
+    }
+
+    /// Runs until no seat changes, returning the number of steps that
+    /// changed any.
+    pub fn run_until_stable(&mut self) -> usize {
+        self.automaton.run_until_stable()
+    }
+}
+
+/// A state of the seating area, from the initial one at index 0
+pub struct Generation {
+    pub index: usize,
+    /// Number of seats that changed since the previous generation
+    pub changed: usize,
+    pub seat_map: SeatMap,
+}
+
+/// Iterator over the successive states of a seating area, which ends when
+/// a step changes no seat
+pub struct Generations {
+    seat_map: SeatMap,
+    index: usize,
+    done: bool,
+}
+
+impl Iterator for Generations {
+    type Item = Generation;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let changed = if self.index == 0 {
+            0
+        } else {
+            self.seat_map.step()
+        };
+        if self.index > 0 && changed == 0 {
+            self.done = true;
+            return None;
+        }
+        let generation = Generation {
+            index: self.index,
+            changed,
+            seat_map: self.seat_map.clone(),
+        };
+        self.index += 1;
+        Some(generation)
+    }
+}
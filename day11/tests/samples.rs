@@ -0,0 +1,158 @@
+use common::grid::{Neighbourhood, Point};
+use day11::{parse_input, simulate, Day11, Policy, SeatMap, Visibility};
+use std::fs::read_to_string;
+use std::path::Path;
+
+common::sample_tests!(Day11);
+
+#[test]
+fn seat_map_steps() {
+    let input = read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("samples/example.txt"),
+    )
+    .unwrap();
+    let seating_area = parse_input(&input).unwrap();
+    let mut seat_map = SeatMap::build(&seating_area, 4, |pos| {
+        pos.neighbours(Neighbourhood::All)
+    });
+    // Every seat is empty, so everyone sits down at once
+    assert_eq!(seat_map.step(), 71);
+    assert_eq!(seat_map.count_occupied(), 71);
+    // The puzzle shows 4 more rounds before the seats stop changing
+    assert_eq!(seat_map.run_until_stable(), 4);
+    assert_eq!(seat_map.count_occupied(), 37);
+    assert_eq!(seat_map.step(), 0);
+}
+
+#[test]
+fn wide_seating_area() {
+    let row = "L".repeat(1000);
+    let input = [row.as_str(); 3].join("\n");
+    let seating_area = parse_input(&input).unwrap();
+    let mut seat_map = SeatMap::build(&seating_area, 4, |pos| {
+        pos.neighbours(Neighbourhood::All)
+    });
+    seat_map.run_until_stable();
+    assert_eq!(seat_map.step(), 0);
+    assert_eq!(seat_map.count_occupied(), 1000);
+}
+
+#[test]
+fn policies() {
+    let input = read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("samples/example.txt"),
+    )
+    .unwrap();
+    let seating_area = parse_input(&input).unwrap();
+    assert_eq!(simulate(&seating_area, &Policy::PART1), 37);
+    assert_eq!(simulate(&seating_area, &Policy::PART2), 26);
+    // Seeing only one position away is the same as watching adjacent seats
+    let short_sight = Policy {
+        visibility: Visibility::LineOfSight { radius: Some(1) },
+        tolerance: 4,
+    };
+    assert_eq!(simulate(&seating_area, &short_sight), 37);
+}
+
+#[test]
+fn generations() {
+    let input = read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("samples/example.txt"),
+    )
+    .unwrap();
+    let seating_area = parse_input(&input).unwrap();
+    let generations: Vec<_> = SeatMap::new(&seating_area, &Policy::PART1)
+        .generations()
+        .collect();
+    // The initial state, then the 5 rounds shown in the puzzle
+    assert_eq!(generations.len(), 6);
+    assert_eq!(generations[0].seat_map.render(), input);
+    assert_eq!(generations[0].changed, 0);
+    assert_eq!(generations[1].changed, 71);
+    assert!(!generations[1].seat_map.render().contains('L'));
+    assert_eq!(
+        generations[2].seat_map.render(),
+        concat!(
+            "#.LL.L#.##\n",
+            "#LLLLLL.L#\n",
+            "L.L.L..L..\n",
+            "#LLL.LL.L#\n",
+            "#.LL.LL.LL\n",
+            "#.LLLL#.##\n",
+            "..L.L.....\n",
+            "#LLLLLLLL#\n",
+            "#.LLLLLL.L\n",
+            "#.#LLLL.##\n",
+        )
+    );
+    let last = generations.last().unwrap();
+    assert_eq!(last.index, 5);
+    assert_eq!(last.seat_map.count_occupied(), 37);
+    assert_eq!(last.seat_map.render().matches('#').count(), 37);
+}
+
+#[test]
+fn display_round_trip() {
+    let input = read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("samples/example.txt"),
+    )
+    .unwrap();
+    assert_eq!(parse_input(&input).unwrap().to_string(), input);
+    // Wider than tall, and different when transposed
+    let input = "L.#L\n#LL.\n";
+    assert_eq!(parse_input(input).unwrap().to_string(), input);
+}
+
+#[test]
+fn orientation() {
+    // Rows are lines and columns are characters: the seats on the top row
+    // only see each other along the row, so nobody there leaves
+    let seating_area = parse_input("L.L.L\n.....\n.....\n").unwrap();
+    let seat_map = SeatMap::new(&seating_area, &Policy::PART2);
+    let last = seat_map.generations().last().unwrap();
+    assert_eq!(last.seat_map.render(), "#.#.#\n.....\n.....\n");
+
+    // The top left seat is the only one watching two seats, so it's the
+    // only one that can't stand two occupied seats in sight
+    let seating_area = parse_input("L.L\nL..\n...\n").unwrap();
+    let policy = Policy {
+        visibility: Visibility::LineOfSight { radius: None },
+        tolerance: 2,
+    };
+    let seat_map = SeatMap::new(&seating_area, &policy);
+    let last = seat_map.generations().last().unwrap();
+    assert_eq!(last.seat_map.render(), "L.#\n#..\n...\n");
+    // Only the seats on row 0, column 2 and row 1, column 0 are occupied,
+    // which a transposed map would swap
+    assert!(last.seat_map.is_occupied(Point::new(2, 0)));
+    assert!(last.seat_map.is_occupied(Point::new(0, 1)));
+    assert!(!last.seat_map.is_occupied(Point::new(0, 2)));
+    assert!(!last.seat_map.is_occupied(Point::new(1, 0)));
+    assert!(!last.seat_map.is_occupied(Point::new(0, 0)));
+
+    // A map wider than tall renders back as it was read, with the occupied
+    // seat at row 1, column 0
+    let input = "L.#L\n#LL.\n";
+    let seat_map = SeatMap::new(&parse_input(input).unwrap(), &Policy::PART1);
+    assert_eq!(seat_map.render(), input);
+    assert!(seat_map.is_occupied(Point::new(0, 1)));
+    assert!(seat_map.is_occupied(Point::new(2, 0)));
+    assert!(!seat_map.is_occupied(Point::new(1, 0)));
+    assert_eq!(seat_map.count_occupied(), 2);
+}
+
+#[test]
+fn ragged_lines() {
+    let err = parse_input("L.L\nL.\nLLL\n").unwrap_err();
+    assert_eq!(err.line(), Some(2));
+    assert!(parse_input("L.L\nL.L\n").is_ok());
+}
+
+#[test]
+fn invalid_character() {
+    let err = parse_input("L.L\nL?L\n").unwrap_err();
+    assert_eq!(err.line(), Some(2));
+    assert!(err
+        .to_string()
+        .contains("invalid character in seating area"));
+}
@@ -1,3 +1,6 @@
+use common::{
+    parse_lines, parse_number, Error, ErrorKind, ParseError, Solution,
+};
 use std::convert::TryFrom;
 use std::str::FromStr;
 
@@ -105,15 +108,21 @@ impl Coordinates {
 }
 
 impl FromStr for Action {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let action = s.get(0..1).ok_or_else(|| "Invalid action")?;
-        let value = s
-            .get(1..)
-            .ok_or_else(|| "Invalid action")?
-            .parse()
-            .map_err(|err| format!("Invalid action value: {}", err))?;
+        let invalid_action = || {
+            ParseError::new(ErrorKind::InvalidSyntax("action")).with_token(s)
+        };
+        let action = s.get(0..1).ok_or_else(invalid_action)?;
+        let value_str = s.get(1..).ok_or_else(invalid_action)?;
+        let value = parse_number(value_str, "action value")
+            .map_err(|err| err.with_column(2))?;
+        let invalid_turn = |reason| {
+            ParseError::new(ErrorKind::InvalidValue(reason))
+                .with_token(value_str)
+                .with_column(2)
+        };
         match action {
             "N" => Ok(Self::Direction(Direction::North, value)),
             "E" => Ok(Self::Direction(Direction::East, value)),
@@ -121,10 +130,39 @@ impl FromStr for Action {
             "W" => Ok(Self::Direction(Direction::West, value)),
             "F" => Ok(Self::Forward(value)),
             "L" if value.rem_euclid(90) == 0 => Ok(Self::Left(value)),
-            "L" => Err(String::from("Left turn must be a multiple of 90")),
+            "L" => Err(invalid_turn("left turn must be a multiple of 90")),
             "R" if value.rem_euclid(90) == 0 => Ok(Self::Right(value)),
-            "R" => Err(String::from("Right turn must be a multiple of 90")),
-            _ => Err(format!("Unknown action: {}", action)),
+            "R" => Err(invalid_turn("right turn must be a multiple of 90")),
+            _ => {
+                Err(ParseError::new(ErrorKind::InvalidValue("unknown action"))
+                    .with_token(action)
+                    .with_column(1))
+            }
         }
     }
 }
+
+pub fn parse_input(input: &str) -> Result<Vec<Action>, ParseError> {
+    parse_lines(input)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    type Input = Vec<Action>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part1::part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part2::part2(input))
+    }
+}
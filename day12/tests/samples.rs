@@ -0,0 +1 @@
+common::sample_tests!(day12::Day12);
@@ -1,3 +1,5 @@
+use common::{column_of, parse_number, Error, ErrorKind, ParseError, Solution};
+
 pub type Bus = u64;
 pub type Timestamp = u64;
 
@@ -29,3 +31,54 @@ pub fn part2(busses: &[Option<Bus>]) -> Timestamp {
     }
     time
 }
+
+pub fn parse_input(
+    input: &str,
+) -> Result<(Timestamp, Vec<Option<Bus>>), ParseError> {
+    let mut lines = input.lines();
+    let timestamp = lines
+        .next()
+        .ok_or_else(|| ParseError::new(ErrorKind::Missing("timestamp")))
+        .and_then(|line| parse_number(line.trim(), "timestamp"))
+        .map_err(|err| err.at_line(1))?;
+    let bus_list = lines
+        .next()
+        .ok_or_else(|| ParseError::new(ErrorKind::Missing("bus list")))?;
+    let busses = bus_list
+        .split(',')
+        .map(|bus| {
+            if bus == "x" {
+                Ok(None)
+            } else {
+                parse_number(bus, "bus number").map(Some).map_err(|err| {
+                    err.at_line(2).with_column(column_of(bus_list, bus))
+                })
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((timestamp, busses))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    type Input = (Timestamp, Vec<Option<Bus>>);
+    type Answer1 = u64;
+    type Answer2 = Timestamp;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        let (timestamp, busses) = input;
+        part1(*timestamp, busses).ok_or(Error::NotFound)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        let (_, busses) = input;
+        Ok(part2(busses))
+    }
+}
@@ -0,0 +1 @@
+common::sample_tests!(day13::Day13);
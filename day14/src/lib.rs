@@ -4,6 +4,9 @@ extern crate lazy_static;
 pub mod part1;
 pub mod part2;
 
+use common::{
+    parse_lines, parse_number, Error, ErrorKind, ParseError, Solution,
+};
 use regex::Regex;
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -14,7 +17,7 @@ pub enum Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
@@ -24,24 +27,46 @@ impl FromStr for Instruction {
             ))
             .unwrap();
         }
-        let captures =
-            REGEX.captures(s).ok_or_else(|| "Invalid instruction")?;
+        let captures = REGEX.captures(s).ok_or_else(|| {
+            ParseError::new(ErrorKind::InvalidSyntax("instruction"))
+                .with_token(s)
+        })?;
         if let Some(mask) = captures.name("mask") {
             Ok(Self::Mask(mask.as_str().to_string()))
         } else {
-            let address = captures
-                .name("addr")
-                .unwrap()
-                .as_str()
-                .parse()
-                .map_err(|err| format!("Invalid address: {}", err))?;
-            let value = captures
-                .name("val")
-                .unwrap()
-                .as_str()
-                .parse()
-                .map_err(|err| format!("Invalid value: {}", err))?;
+            let number = |name, what| {
+                let capture = captures.name(name).unwrap();
+                parse_number(capture.as_str(), what)
+                    .map_err(|err| err.with_column(capture.start() + 1))
+            };
+            let address = number("addr", "address")?;
+            let value = number("val", "value")?;
             Ok(Self::Mem(address, value))
         }
     }
 }
+
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines(input)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    type Input = Vec<Instruction>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        part1::part1(input).ok_or(Error::NotFound)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        part2::part2(input)
+    }
+}
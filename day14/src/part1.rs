@@ -29,7 +29,7 @@ struct Program {
 }
 
 impl Program {
-    fn run(&mut self, instructions: &[Instruction]) -> Result<(), String> {
+    fn run(&mut self, instructions: &[Instruction]) -> Result<(), ParseError> {
         for instr in instructions {
             match instr {
                 Instruction::Mask(mask) => self.bitmask = mask.parse()?,
@@ -53,7 +53,7 @@ pub fn part1(instructions: &[Instruction]) -> Option<u64> {
 }
 
 impl FromStr for Bitmask {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let or_mask =
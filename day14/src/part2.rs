@@ -1,75 +1,294 @@
 use super::*;
 
+/// Number of bits in an address
+const ADDRESS_BITS: usize = 36;
+
+/// A set of addresses written as a mask of `0`, `1` and `X`: the addresses
+/// whose bits match the mask wherever it isn't `X`. Sets can be combined
+/// without listing their addresses, of which there may be up to 2^36.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AddressSet {
+    /// Bits that are the same in every address, 0 where floating
+    fixed: u64,
+    /// Bits that take both values
+    floating: u64,
+}
+
+impl AddressSet {
+    pub fn new(fixed: u64, floating: u64) -> Self {
+        Self {
+            fixed: fixed & !floating,
+            floating,
+        }
+    }
+
+    /// Number of addresses in the set
+    pub fn count(&self) -> u64 {
+        1 << self.floating.count_ones()
+    }
+
+    pub fn contains(&self, address: u64) -> bool {
+        address & !self.floating == self.fixed
+    }
+
+    /// The addresses in both sets, if any.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let fixed_in_both = !self.floating & !other.floating;
+        if (self.fixed ^ other.fixed) & fixed_in_both != 0 {
+            return None;
+        }
+        Some(Self {
+            fixed: self.fixed | other.fixed,
+            floating: self.floating & other.floating,
+        })
+    }
+}
+
+/// The decoder's memory, as the sets of addresses written in order, each
+/// with its value.
+#[derive(Default)]
+pub struct Memory {
+    writes: Vec<(AddressSet, u64)>,
+}
+
+impl Memory {
+    /// Writes the value to every address in the set.
+    pub fn write(&mut self, addresses: AddressSet, value: u64) {
+        self.writes.push((addresses, value));
+    }
+
+    pub fn read(&self, address: u64) -> u64 {
+        self.writes
+            .iter()
+            .rev()
+            .find(|(set, _)| set.contains(address))
+            .map_or(0, |&(_, value)| value)
+    }
+
+    /// Sum of the values at every address, or `None` if it doesn't fit in
+    /// 64 bits.
+    ///
+    /// Each write only counts for the addresses that no later write
+    /// overwrote, which are counted without listing addresses.
+    pub fn sum(&self) -> Option<u64> {
+        let mut sum = 0_u64;
+        for (index, &(set, value)) in self.writes.iter().enumerate() {
+            if value == 0 {
+                continue;
+            }
+            let constraints = self.writes[index + 1..]
+                .iter()
+                .filter(|(later, _)| set.intersection(later).is_some())
+                .map(|(later, _)| {
+                    let bits = set.floating & !later.floating;
+                    (bits, later.fixed & bits)
+                })
+                .collect();
+            let count = count_unmatched(set.floating, constraints);
+            sum = sum.checked_add(count.checked_mul(value)?)?;
+        }
+        Some(sum)
+    }
+}
+
+/// Values of some floating bits that another address set covers, given by
+/// the floating bits that it fixes and their values
+type Constraint = (u64, u64);
+
+/// Number of values of the floating bits that match none of the
+/// constraints.
+///
+/// Constraints that only match values another one matches are dropped, and
+/// floating bits that no constraint looks at just double the count. Groups
+/// of constraints that look at different bits leave out shares of the
+/// values that multiply. Otherwise the bit that matters most to the
+/// constraints looking at the fewest bits is set to 0 and to 1 in turn.
+fn count_unmatched(floating: u64, mut constraints: Vec<Constraint>) -> u64 {
+    constraints.sort_unstable_by_key(|&(bits, value)| {
+        (bits.count_ones(), bits, value)
+    });
+    constraints.dedup();
+    let mut kept: Vec<Constraint> = Vec::with_capacity(constraints.len());
+    for (bits, value) in constraints {
+        if bits == 0 {
+            return 0;
+        }
+        let matched_by_kept = kept.iter().any(|&(kept_bits, kept_value)| {
+            kept_bits & !bits == 0 && value & kept_bits == kept_value
+        });
+        if !matched_by_kept {
+            kept.push((bits, value));
+        }
+    }
+    let constraints = kept;
+
+    // A constraint on a single bit leaves only the other value of the bit
+    if let Some(&(bit, value)) = constraints
+        .iter()
+        .find(|&&(bits, _)| bits.count_ones() == 1)
+    {
+        let rest = constraints
+            .iter()
+            .filter(|&&(bits, fixed)| bits & bit == 0 || fixed & bit != value)
+            .map(|&(bits, fixed)| (bits & !bit, fixed & !bit))
+            .collect();
+        return count_unmatched(floating & !bit, rest);
+    }
+
+    let looked_at = constraints.iter().fold(0, |all, &(bits, _)| all | bits);
+    let ignored = (floating & !looked_at).count_ones();
+    let count = match constraints.as_slice() {
+        [] => 1,
+        &[(bits, _)] => (1 << bits.count_ones()) - 1,
+        _ => {
+            let groups = independent_groups(&constraints);
+            if groups.len() > 1 {
+                groups
+                    .into_iter()
+                    .map(|(bits, group)| count_unmatched(bits, group))
+                    .product()
+            } else {
+                split_count(looked_at, &constraints)
+            }
+        }
+    };
+    count << ignored
+}
+
+/// Counts values matching no constraint as those with the most telling bit
+/// at 0 plus those with it at 1.
+fn split_count(floating: u64, constraints: &[Constraint]) -> u64 {
+    let bit = (0..ADDRESS_BITS)
+        .map(|bit| 1 << bit)
+        .filter(|&bit| floating & bit != 0)
+        .max_by_key(|&bit| {
+            // Each constraint counts for the share of values it matches
+            constraints
+                .iter()
+                .filter(|&&(bits, _)| bits & bit != 0)
+                .map(|&(bits, _)| 1_u64 << (64 - bits.count_ones()))
+                .fold(0_u64, u64::saturating_add)
+        })
+        .unwrap();
+    [0, bit]
+        .iter()
+        .map(|&value| {
+            let rest = constraints
+                .iter()
+                .filter(|&&(bits, fixed)| {
+                    bits & bit == 0 || fixed & bit == value
+                })
+                .map(|&(bits, fixed)| (bits & !bit, fixed & !bit))
+                .collect();
+            count_unmatched(floating & !bit, rest)
+        })
+        .sum()
+}
+
+/// Splits constraints into groups that look at different bits, with the
+/// bits each group looks at.
+fn independent_groups(
+    constraints: &[Constraint],
+) -> Vec<(u64, Vec<Constraint>)> {
+    let mut groups: Vec<(u64, Vec<Constraint>)> = Vec::new();
+    for &(bits, value) in constraints {
+        let mut group = (bits, vec![(bits, value)]);
+        let mut index = 0;
+        while index < groups.len() {
+            if groups[index].0 & group.0 != 0 {
+                let (other_bits, others) = groups.swap_remove(index);
+                group.0 |= other_bits;
+                group.1.extend(others);
+            } else {
+                index += 1;
+            }
+        }
+        groups.push(group);
+    }
+    groups
+}
+
 #[derive(Default)]
 struct Bitmask {
     or_mask: u64,
-    xor_masks: Vec<u64>,
+    floating: u64,
 }
 
 impl Bitmask {
-    fn apply(&self, value: u64) -> Vec<u64> {
-        let mut mod_values = vec![value | self.or_mask];
-        for mask in self.xor_masks.iter() {
-            for index in 0..mod_values.len() {
-                mod_values.push(mod_values[index] ^ mask);
-            }
-        }
-        mod_values
+    fn apply(&self, address: u64) -> AddressSet {
+        AddressSet::new(address | self.or_mask, self.floating)
     }
 }
 
 #[derive(Default)]
 struct Program {
-    memory: HashMap<u64, u64>,
+    memory: Memory,
     bitmask: Bitmask,
 }
 
 impl Program {
-    fn run(&mut self, instructions: &[Instruction]) -> Result<(), String> {
+    fn run(&mut self, instructions: &[Instruction]) -> Result<(), ParseError> {
         for instr in instructions {
             match instr {
                 Instruction::Mask(mask) => self.bitmask = mask.parse()?,
                 &Instruction::Mem(address, value) => {
-                    for mod_address in self.bitmask.apply(address) {
-                        self.memory.insert(mod_address, value);
-                    }
+                    self.memory.write(self.bitmask.apply(address), value);
                 }
             }
         }
         Ok(())
     }
-
-    fn mem_sum(&self) -> u64 {
-        self.memory.values().sum()
-    }
 }
 
-pub fn part2(instructions: &[Instruction]) -> Option<u64> {
+pub fn part2(instructions: &[Instruction]) -> Result<u64, Error> {
     let mut program = Program::default();
-    program.run(instructions).ok();
-    Some(program.mem_sum())
+    program.run(instructions)?;
+    program.memory.sum().ok_or_else(|| {
+        Error::Unsolvable("sum of memory values overflows".to_string())
+    })
 }
 
-impl FromStr for Bitmask {
-    type Err = String;
+impl FromStr for AddressSet {
+    type Err = ParseError;
 
+    /// Parses a mask of up to 36 `0`, `1` and `X`, most significant bit
+    /// first.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let or_mask =
-            s.chars()
-                .rev()
-                .zip(0..)
-                .fold(0, |mask, (ch, bit)| match ch {
-                    '1' => mask | 2_u64.pow(bit),
-                    _ => mask,
-                });
-        let xor_masks = s
-            .chars()
-            .rev()
-            .zip(0..)
-            .filter(|&(ch, _)| ch == 'X')
-            .map(|(_, bit)| 2_u64.pow(bit))
-            .collect::<Vec<_>>();
+        if s.len() > ADDRESS_BITS {
+            return Err(ParseError::new(ErrorKind::InvalidValue(
+                "mask longer than 36 bits",
+            ))
+            .with_token(s));
+        }
+        let mut set = Self::new(0, 0);
+        for (ch, column) in s.chars().zip(1..) {
+            set.fixed <<= 1;
+            set.floating <<= 1;
+            match ch {
+                '0' => {}
+                '1' => set.fixed |= 1,
+                'X' => set.floating |= 1,
+                _ => {
+                    return Err(ParseError::new(ErrorKind::InvalidSyntax(
+                        "mask",
+                    ))
+                    .with_token(ch)
+                    .with_column(column))
+                }
+            }
+        }
+        Ok(set)
+    }
+}
 
-        Ok(Self { or_mask, xor_masks })
+impl FromStr for Bitmask {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let AddressSet { fixed, floating } = s.parse()?;
+        Ok(Self {
+            or_mask: fixed,
+            floating,
+        })
     }
 }
@@ -0,0 +1 @@
+common::sample_tests!(day14::Day14);
@@ -1,3 +1,4 @@
+use common::{column_of, parse_number, Error, ParseError, Solution};
 use std::collections::HashMap;
 use std::convert::TryInto;
 
@@ -25,3 +26,36 @@ pub fn part1(numbers: &[i32]) -> Option<i32> {
 pub fn part2(numbers: &[i32]) -> Option<i32> {
     find_nth_number(numbers, 30_000_000)
 }
+
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    input
+        .split(',')
+        .map(|number| {
+            let number = number.trim();
+            parse_number(number, "starting number").map_err(|err| {
+                err.at_line(1).with_column(column_of(input, number))
+            })
+        })
+        .collect()
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        part1(input).ok_or(Error::NotFound)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        part2(input).ok_or(Error::NotFound)
+    }
+}
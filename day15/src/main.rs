@@ -1,48 +1,7 @@
-use clap::{crate_description, App, Arg};
-use day15::*;
-use std::fs::read_to_string;
-use std::process::exit;
+use clap::crate_description;
+use common::runner;
+use day15::Day15;
 
 fn main() {
-    let args = App::new(crate_description!())
-        .arg(
-            Arg::with_name("INPUT")
-                .help("File with puzzle input")
-                .required(true)
-                .index(1),
-        )
-        .get_matches();
-
-    println!(crate_description!());
-
-    let input = match read_input(args.value_of("INPUT").unwrap()) {
-        Ok(data) => data,
-        Err(err) => {
-            println!("Failed to read input: {}", err);
-            exit(2);
-        }
-    };
-
-    match part1(&input) {
-        Some(result) => println!("Part 1: {}", result),
-        None => println!("Part 1: not found"),
-    };
-
-    match part2(&input) {
-        Some(result) => println!("Part 2: {}", result),
-        None => println!("Part 2: not found"),
-    };
-}
-
-fn read_input(filename: &str) -> Result<Vec<i32>, String> {
-    read_to_string(filename)
-        .map_err(|err| err.to_string())?
-        .split(',')
-        .map(|number| {
-            number
-                .trim()
-                .parse()
-                .map_err(|err| format!("Invalid number '{}': {}", number, err))
-        })
-        .collect::<Result<_, _>>()
+    runner::main::<Day15>(crate_description!());
 }
@@ -0,0 +1 @@
+common::sample_tests!(day15::Day15);
@@ -1,9 +1,12 @@
 #[macro_use]
 extern crate lazy_static;
 
+use common::{
+    column_of, parse_number, sections, Error, ErrorKind, ParseError, Section,
+    Solution,
+};
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 pub type Ticket = Vec<u64>;
@@ -129,7 +132,7 @@ pub fn part2(
 }
 
 impl FromStr for Rule {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
@@ -140,17 +143,16 @@ impl FromStr for Rule {
             .unwrap();
         }
 
-        let captures = REGEX.captures(s).ok_or_else(|| "Invalid rule")?;
+        let captures = REGEX.captures(s).ok_or_else(|| {
+            ParseError::new(ErrorKind::InvalidSyntax("rule")).with_token(s)
+        })?;
         let name = captures.name("name").unwrap().as_str().to_string();
         let numbers = ["start1", "end1", "start2", "end2"]
             .iter()
             .map(|field| {
-                captures
-                    .name(field)
-                    .unwrap()
-                    .as_str()
-                    .parse()
-                    .map_err(|err| format!("Invalid number: {}", err))
+                let capture = captures.name(field).unwrap();
+                parse_number(capture.as_str(), "range limit")
+                    .map_err(|err| err.with_column(capture.start() + 1))
             })
             .collect::<Result<Vec<_>, _>>()?;
         let range1 = (numbers[0], numbers[1]);
@@ -164,12 +166,92 @@ impl FromStr for Rule {
     }
 }
 
-pub fn parse_ticket(line: &str) -> Result<Ticket, String> {
+pub fn parse_ticket(line: &str) -> Result<Ticket, ParseError> {
     line.split(',')
         .map(|value| {
-            value.trim().parse().map_err(|err: ParseIntError| {
-                format!("Invalid ticket value '{}': {}", value, err)
-            })
+            let value = value.trim();
+            parse_number(value, "ticket value")
+                .map_err(|err| err.with_column(column_of(line, value)))
         })
         .collect::<Result<Vec<_>, _>>()
 }
+
+pub fn parse_input(
+    input: &str,
+) -> Result<(Vec<Rule>, Ticket, Vec<Ticket>), ParseError> {
+    let sections = sections(input);
+    let (rules, your_ticket, nearby_tickets) = match sections.as_slice() {
+        [rules, your_ticket, nearby_tickets] => {
+            (rules, your_ticket, nearby_tickets)
+        }
+        _ => {
+            return Err(ParseError::new(ErrorKind::Expected(
+                "rules, your ticket and nearby tickets sections",
+            )))
+        }
+    };
+
+    let rules = rules
+        .lines()
+        .map(|(line, line_num)| {
+            line.parse()
+                .map_err(|err: ParseError| err.at_line(line_num))
+        })
+        .collect::<Result<_, _>>()?;
+    let your_ticket = parse_tickets(your_ticket, "your ticket:")?
+        .pop()
+        .ok_or_else(|| {
+            ParseError::new(ErrorKind::Missing("your ticket"))
+                .at_line(your_ticket.line_num)
+        })?;
+    let nearby_tickets = parse_tickets(nearby_tickets, "nearby tickets:")?;
+
+    Ok((rules, your_ticket, nearby_tickets))
+}
+
+fn parse_tickets(
+    section: &Section,
+    header: &'static str,
+) -> Result<Vec<Ticket>, ParseError> {
+    let mut lines = section.lines();
+    match lines.next() {
+        Some((line, _)) if line.trim() == header => (),
+        Some((line, line_num)) => {
+            return Err(ParseError::new(ErrorKind::Expected(header))
+                .with_token(line)
+                .at_line(line_num))
+        }
+        None => {
+            return Err(ParseError::new(ErrorKind::Expected(header))
+                .at_line(section.line_num))
+        }
+    }
+    lines
+        .map(|(line, line_num)| {
+            parse_ticket(line).map_err(|err| err.at_line(line_num))
+        })
+        .collect()
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    type Input = (Vec<Rule>, Ticket, Vec<Ticket>);
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        let (rules, _, nearby_tickets) = input;
+        Ok(part1(rules, nearby_tickets))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        let (rules, your_ticket, nearby_tickets) = input;
+        part2(rules, your_ticket, nearby_tickets).ok_or(Error::NotFound)
+    }
+}
@@ -0,0 +1 @@
+common::sample_tests!(day16::Day16);
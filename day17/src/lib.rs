@@ -1,147 +1,247 @@
-use std::collections::{HashMap, HashSet};
-use std::hash::Hash;
+pub mod symmetric;
+
+use common::automaton::{Automaton, Cubic, Rule};
+use common::grid::SparseGrid;
+use common::{Error, ParseError, Solution};
+use std::collections::{BTreeMap, HashSet};
 use std::str::FromStr;
 
-pub fn part1(input: &str) -> Result<usize, String> {
-    let mut grid: Grid<Cube3D> = input.parse()?;
-    for _cycle in 1..=6 {
-        grid = grid.update();
-    }
-    Ok(grid.count_active())
+pub fn part1(slice: &Slice) -> usize {
+    simulate::<3>(slice, &conway_rule(), 6)
 }
 
-pub fn part2(input: &str) -> Result<usize, String> {
-    let mut grid: Grid<Cube4D> = input.parse()?;
-    for _cycle in 1..=6 {
-        grid = grid.update();
-    }
-    Ok(grid.count_active())
+pub fn part2(slice: &Slice) -> usize {
+    simulate::<4>(slice, &conway_rule(), 6)
+}
+
+/// The rule of the Conway Cubes: an active cube stays active with 2 or 3
+/// active neighbours, and an inactive cube becomes active with exactly 3.
+pub fn conway_rule() -> Rule {
+    Rule::new(&[3], &[2, 3])
 }
 
-trait Cube {
-    fn neighbours(&self) -> Box<dyn Iterator<Item = Self> + '_>;
-    fn from_2d(coord0: i32, coord1: i32) -> Self;
+/// Runs the given number of cycles in N dimensions, starting with the slice
+/// at 0 in every dimension after the first two, and returns the number of
+/// active cubes.
+pub fn simulate<const N: usize>(
+    slice: &Slice,
+    rule: &Rule,
+    cycles: usize,
+) -> usize {
+    let mut automaton =
+        Automaton::new(Cubic::<N>::new(), rule.clone(), slice.cubes::<N>());
+    automaton.run(cycles);
+    automaton.count_active()
 }
 
-struct Grid<T> {
-    active: HashSet<T>,
+/// Highest number of dimensions supported by [`pocket`]
+pub const MAX_DIMENSIONS: usize = 8;
+
+/// How to run a simulation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    /// Keeps every active cube
+    Full,
+    /// Keeps a single cube out of those that are in the same state by
+    /// symmetry, see [`symmetric`]
+    Symmetric,
 }
 
-impl<T: Cube + Clone + Hash + Eq> Grid<T> {
+/// A pocket dimension being simulated, whatever its number of dimensions
+pub trait Pocket {
+    /// Runs one cycle.
+    fn cycle(&mut self);
+    fn count_active(&self) -> usize;
+    /// Every active cube, in no particular order
+    fn active_cubes(&self) -> Vec<Vec<i32>>;
+}
+
+impl<const N: usize> Pocket for Automaton<Cubic<N>> {
+    fn cycle(&mut self) {
+        self.step();
+    }
+
     fn count_active(&self) -> usize {
-        self.active.len()
+        Automaton::count_active(self)
     }
 
-    fn update(&self) -> Self {
-        let mut active = HashSet::new();
-        let mut inactive: HashMap<T, usize> = HashMap::new();
-
-        for cube in self.active.iter() {
-            let mut active_neighbours = 0;
-            for neighbour in cube.neighbours() {
-                if self.active.contains(&neighbour) {
-                    active_neighbours += 1;
-                } else {
-                    inactive
-                        .entry(neighbour)
-                        .and_modify(|count| *count += 1)
-                        .or_insert(1);
-                }
-            }
-            if active_neighbours == 2 || active_neighbours == 3 {
-                active.insert(cube.clone());
+    fn active_cubes(&self) -> Vec<Vec<i32>> {
+        self.active().iter().map(|cube| cube.to_vec()).collect()
+    }
+}
+
+/// Creates a simulation starting with the slice, with the number of
+/// dimensions chosen at runtime. Fails unless there are between 2 and
+/// [`MAX_DIMENSIONS`], or if the rule counts more neighbours than a cube has.
+pub fn pocket(
+    slice: &Slice,
+    dimensions: usize,
+    rule: &Rule,
+    mode: Mode,
+) -> Result<Box<dyn Pocket>, String> {
+    fn create<const N: usize>(
+        slice: &Slice,
+        rule: &Rule,
+        mode: Mode,
+    ) -> Result<Box<dyn Pocket>, String> {
+        rule.check(&Cubic::<N>::new())?;
+        Ok(match mode {
+            Mode::Full => Box::new(Automaton::new(
+                Cubic::<N>::new(),
+                rule.clone(),
+                slice.cubes::<N>(),
+            )),
+            Mode::Symmetric => {
+                Box::new(symmetric::SymmetricPocket::<N>::new(slice, rule))
             }
+        })
+    }
+
+    let pocket = match dimensions {
+        2 => create::<2>(slice, rule, mode),
+        3 => create::<3>(slice, rule, mode),
+        4 => create::<4>(slice, rule, mode),
+        5 => create::<5>(slice, rule, mode),
+        6 => create::<6>(slice, rule, mode),
+        7 => create::<7>(slice, rule, mode),
+        8 => create::<8>(slice, rule, mode),
+        _ => {
+            return Err(format!(
+                "can only simulate from 2 to {} dimensions",
+                MAX_DIMENSIONS
+            ))
         }
+    };
+    pocket
+}
 
-        active.extend(
-            inactive
-                .drain()
-                .filter(|(_, active_neighbours)| *active_neighbours == 3)
-                .map(|(cube, _)| cube),
-        );
+/// Like [`simulate`], with the number of dimensions chosen at runtime.
+/// Fails like [`pocket`].
+pub fn simulate_in(
+    slice: &Slice,
+    dimensions: usize,
+    rule: &Rule,
+    cycles: usize,
+    mode: Mode,
+) -> Result<usize, String> {
+    let mut pocket = pocket(slice, dimensions, rule, mode)?;
+    for _ in 0..cycles {
+        pocket.cycle();
+    }
+    Ok(pocket.count_active())
+}
 
-        Self { active }
+/// Name of each coordinate of a cube, when writing where a slice is
+fn coordinate_name(index: usize) -> String {
+    match index {
+        0 => "x".to_string(),
+        1 => "y".to_string(),
+        2 => "z".to_string(),
+        3 => "w".to_string(),
+        _ => format!("d{}", index + 1),
     }
 }
 
-#[derive(Clone, Eq, Hash, PartialEq)]
-struct Cube3D(i32, i32, i32);
-
-impl Cube for Cube3D {
-    fn neighbours(&self) -> Box<dyn Iterator<Item = Self> + '_> {
-        Box::new(
-            (-1..=1)
-                .flat_map(|delta0| {
-                    (-1..=1).flat_map(move |delta1| {
-                        (-1..=1).map(move |delta2| (delta0, delta1, delta2))
-                    })
-                })
-                .filter(|(delta0, delta1, delta2)| {
-                    *delta0 != 0 || *delta1 != 0 || *delta2 != 0
-                })
-                .map(move |(delta0, delta1, delta2)| {
-                    Self(self.0 + delta0, self.1 + delta1, self.2 + delta2)
-                }),
-        )
+/// Writes the active cubes as the 2D slices of the pocket dimension that
+/// have any, in the same layout as the puzzle: each slice is headed by its
+/// position in the dimensions after the first two (e.g. `z=-1, w=0`), and
+/// all slices cover the same area.
+pub fn render(cubes: &[Vec<i32>]) -> String {
+    let (min_x, max_x, min_y, max_y) = cubes.iter().fold(
+        (i32::MAX, i32::MIN, i32::MAX, i32::MIN),
+        |(min_x, max_x, min_y, max_y), cube| {
+            (
+                min_x.min(cube[0]),
+                max_x.max(cube[0]),
+                min_y.min(cube[1]),
+                max_y.max(cube[1]),
+            )
+        },
+    );
+    let mut slices: BTreeMap<&[i32], HashSet<(i32, i32)>> = BTreeMap::new();
+    for cube in cubes {
+        slices
+            .entry(&cube[2..])
+            .or_default()
+            .insert((cube[0], cube[1]));
     }
 
-    fn from_2d(coord0: i32, coord1: i32) -> Self {
-        Self(coord0, coord1, 0)
+    let mut text = String::new();
+    for (position, active) in slices {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        let header: Vec<_> = position
+            .iter()
+            .enumerate()
+            .map(|(index, coord)| {
+                format!("{}={}", coordinate_name(index + 2), coord)
+            })
+            .collect();
+        if !header.is_empty() {
+            text.push_str(&header.join(", "));
+            text.push('\n');
+        }
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                text.push(if active.contains(&(x, y)) { '#' } else { '.' });
+            }
+            text.push('\n');
+        }
     }
+    text
 }
 
-#[derive(Clone, Eq, Hash, PartialEq)]
-struct Cube4D(i32, i32, i32, i32);
-
-impl Cube for Cube4D {
-    fn neighbours(&self) -> Box<dyn Iterator<Item = Self> + '_> {
-        Box::new(
-            (-1..=1)
-                .flat_map(|delta0| {
-                    (-1..=1).flat_map(move |delta1| {
-                        (-1..=1).flat_map(move |delta2| {
-                            (-1..=1).map(move |delta3| {
-                                (delta0, delta1, delta2, delta3)
-                            })
-                        })
-                    })
-                })
-                .filter(|(delta0, delta1, delta2, delta3)| {
-                    *delta0 != 0 || *delta1 != 0 || *delta2 != 0 || *delta3 != 0
-                })
-                .map(move |(delta0, delta1, delta2, delta3)| {
-                    Self(
-                        self.0 + delta0,
-                        self.1 + delta1,
-                        self.2 + delta2,
-                        self.3 + delta3,
-                    )
-                }),
-        )
-    }
+/// The initial state: a 2D slice of the pocket dimension
+pub struct Slice {
+    active: Vec<(i32, i32)>,
+}
 
-    fn from_2d(coord0: i32, coord1: i32) -> Self {
-        Self(coord0, coord1, 0, 0)
+impl Slice {
+    /// The active cubes in N dimensions, at 0 in every dimension after the
+    /// first two.
+    fn cubes<const N: usize>(&self) -> impl Iterator<Item = [i32; N]> + '_ {
+        self.active.iter().map(|&(x, y)| {
+            let mut cube = [0; N];
+            cube[0] = x;
+            cube[1] = y;
+            cube
+        })
     }
 }
 
-impl<T: Cube + Hash + Eq> FromStr for Grid<T> {
-    type Err = String;
+impl FromStr for Slice {
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let active = s
-            .lines()
-            .zip(0..)
-            .flat_map(|(line, coord0)| {
-                line.chars().zip(0..).filter(|&(ch, _)| ch != '.').map(
-                    move |(ch, coord1)| match ch {
-                        '#' => Ok(T::from_2d(coord0, coord1)),
-                        _ => Err(format!("Invalid character in grid: {}", ch)),
-                    },
-                )
-            })
-            .collect::<Result<_, _>>()?;
-
+        let grid: SparseGrid<bool> = s.parse()?;
+        let active =
+            grid.cells().map(|(point, _)| (point.x, point.y)).collect();
         Ok(Self { active })
     }
 }
+
+pub fn parse_input(input: &str) -> Result<Slice, ParseError> {
+    input.parse()
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+    type Input = Slice;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part2(input))
+    }
+}
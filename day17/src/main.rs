@@ -1,34 +1,126 @@
-use clap::{crate_description, App, Arg};
-use day17::*;
-use std::fs::read_to_string;
+use clap::{crate_description, Arg, ArgMatches};
+use common::automaton::Rule;
+use common::runner;
+use day17::{parse_input, pocket, render, Day17, Mode, MAX_DIMENSIONS};
 use std::process::exit;
 
 fn main() {
-    let args = App::new(crate_description!())
+    let args = runner::app(crate_description!())
         .arg(
-            Arg::with_name("INPUT")
-                .help("File with puzzle input")
-                .required(true)
-                .index(1),
+            Arg::with_name("dimensions")
+                .long("dimensions")
+                .value_name("N")
+                .validator(|value| match value.parse::<usize>() {
+                    Ok(dimensions)
+                        if (2..=MAX_DIMENSIONS).contains(&dimensions) =>
+                    {
+                        Ok(())
+                    }
+                    _ => Err(format!(
+                        "must be a number from 2 to {}",
+                        MAX_DIMENSIONS
+                    )),
+                })
+                .conflicts_with_all(&["format", "part", "check"])
+                .help("Runs a single simulation in this many dimensions"),
+        )
+        .arg(
+            Arg::with_name("cycles")
+                .long("cycles")
+                .value_name("N")
+                .requires("dimensions")
+                .default_value_if("dimensions", None, "6")
+                .validator(|value| {
+                    value
+                        .parse::<usize>()
+                        .map(|_| ())
+                        .map_err(|_| "must be a number".to_string())
+                })
+                .help("Number of cycles of the simulation"),
+        )
+        .arg(
+            Arg::with_name("rule")
+                .long("rule")
+                .value_name("RULE")
+                .requires("dimensions")
+                .default_value_if("dimensions", None, "B3/S23")
+                .validator(|value| match value.parse::<Rule>() {
+                    Ok(rule) if rule.next_state(false, 0) => Err(
+                        "cubes can't become active with no active neighbours"
+                            .to_string(),
+                    ),
+                    Ok(_) => Ok(()),
+                    Err(err) => Err(err.to_string()),
+                })
+                .help(
+                    "Rule of the simulation, e.g. B3/S23 for cubes to \
+                     become active with 3 active neighbours and stay active \
+                     with 2 or 3",
+                ),
+        )
+        .arg(
+            Arg::with_name("symmetric")
+                .long("symmetric")
+                .requires("dimensions")
+                .help(
+                    "Only keeps track of one cube out of those that are \
+                     symmetric, which is faster in higher dimensions",
+                ),
+        )
+        .arg(
+            Arg::with_name("render")
+                .long("render")
+                .requires("dimensions")
+                .help("Prints the active cubes before and after every cycle"),
         )
         .get_matches();
 
-    println!(crate_description!());
+    if args.is_present("dimensions") {
+        simulate(&args);
+    } else {
+        runner::run::<Day17>(&args);
+    }
+}
 
-    let input = match read_to_string(args.value_of("INPUT").unwrap()) {
-        Ok(data) => data,
-        Err(err) => {
-            println!("Failed to read input: {}", err);
-            exit(2);
-        }
+/// Runs the simulation requested on the command line for every input.
+fn simulate(args: &ArgMatches) {
+    let dimensions = args.value_of("dimensions").unwrap().parse().unwrap();
+    let rule: Rule = args.value_of("rule").unwrap().parse().unwrap();
+    let cycles = args.value_of("cycles").unwrap().parse().unwrap();
+    let mode = if args.is_present("symmetric") {
+        Mode::Symmetric
+    } else {
+        Mode::Full
     };
-
-    match part1(&input) {
-        Ok(result) => println!("Part 1: {}", result),
-        Err(err) => println!("Part 1: {}", err),
-    }
-    match part2(&input) {
-        Ok(result) => println!("Part 2: {}", result),
-        Err(err) => println!("Part 2: {}", err),
+    for filename in runner::input_args(args) {
+        let slice = runner::parse_file(filename, parse_input);
+        match slice {
+            Ok(slice) => {
+                let mut pocket = pocket(&slice, dimensions, &rule, mode)
+                    .unwrap_or_else(|err| {
+                        println!("{}", err);
+                        exit(2);
+                    });
+                for cycle in 0..=cycles {
+                    if cycle > 0 {
+                        pocket.cycle();
+                    }
+                    if args.is_present("render") {
+                        println!("{}: cycle {}:\n", filename, cycle);
+                        println!("{}", render(&pocket.active_cubes()));
+                    }
+                }
+                let active = pocket.count_active();
+                println!(
+                    "{}: {} active cubes after {} cycles in {} dimensions \
+                     with rule {}",
+                    filename, active, cycles, dimensions, rule
+                );
+            }
+            Err(err) => {
+                println!("{}: {}", filename, err);
+                exit(2);
+            }
+        }
     }
 }
@@ -0,0 +1,150 @@
+//! A simulation that only keeps track of one cube out of each set of cubes
+//! that are bound to be in the same state.
+//!
+//! The initial slice lies at 0 in every dimension after the first two, so
+//! mirroring any of those coordinates, or swapping two of them, maps the
+//! pocket dimension onto itself at every cycle. Each cube is stored in its
+//! canonical form, with those coordinates made non-negative and sorted, and
+//! stands for all the cubes it can be mapped to.
+
+use crate::{Pocket, Slice};
+use common::automaton::{Cubic, Rule, Topology};
+use std::collections::{HashMap, HashSet};
+
+/// Same as [`crate::simulate`], storing only canonical cubes.
+///
+/// Panics if the rule lets cubes with no active neighbours become active.
+pub fn simulate<const N: usize>(
+    slice: &Slice,
+    rule: &Rule,
+    cycles: usize,
+) -> usize {
+    let mut pocket = SymmetricPocket::<N>::new(slice, rule);
+    for _ in 0..cycles {
+        pocket.cycle();
+    }
+    pocket.count_active()
+}
+
+/// A pocket dimension in N dimensions, storing only canonical cubes
+pub struct SymmetricPocket<const N: usize> {
+    topology: Cubic<N>,
+    rule: Rule,
+    active: HashSet<[i32; N]>,
+}
+
+impl<const N: usize> SymmetricPocket<N> {
+    /// Starts with the slice at 0 in every dimension after the first two.
+    ///
+    /// Panics if the rule lets cubes with no active neighbours become
+    /// active.
+    pub fn new(slice: &Slice, rule: &Rule) -> Self {
+        assert!(
+            !rule.next_state(false, 0),
+            "Cubes can't become active with no active neighbours"
+        );
+        Self {
+            topology: Cubic::new(),
+            rule: rule.clone(),
+            active: slice.cubes::<N>().collect(),
+        }
+    }
+}
+
+impl<const N: usize> Pocket for SymmetricPocket<N> {
+    fn cycle(&mut self) {
+        // Adjacent pairs of cubes between each canonical cube's set and
+        // the active ones
+        let mut adjacent: HashMap<[i32; N], usize> = HashMap::new();
+        for cube in self.active.iter() {
+            let weight = weight(cube);
+            for neighbour in self.topology.neighbours(cube) {
+                *adjacent.entry(canonical(neighbour)).or_insert(0) += weight;
+            }
+        }
+        let rule = &self.rule;
+        let active = &self.active;
+        self.active = adjacent
+            .keys()
+            .chain(active.iter())
+            .filter(|cube| {
+                let count = adjacent.get(*cube).copied().unwrap_or(0);
+                // Every cube in the set has the same number of active
+                // neighbours
+                let active_neighbours = count / weight(cube);
+                rule.next_state(active.contains(*cube), active_neighbours)
+            })
+            .copied()
+            .collect();
+    }
+
+    fn count_active(&self) -> usize {
+        self.active.iter().map(weight).sum()
+    }
+
+    fn active_cubes(&self) -> Vec<Vec<i32>> {
+        self.active
+            .iter()
+            .flat_map(|cube| images(*cube))
+            .map(|cube| cube.to_vec())
+            .collect()
+    }
+}
+
+/// Every cube a canonical cube stands for, found by mirroring one
+/// coordinate after the first two or swapping two adjacent ones until no
+/// new cube turns up.
+fn images<const N: usize>(cube: [i32; N]) -> HashSet<[i32; N]> {
+    let mut images = HashSet::new();
+    let mut pending = vec![cube];
+    while let Some(cube) = pending.pop() {
+        if !images.insert(cube) {
+            continue;
+        }
+        for index in 2..N {
+            let mut mirrored = cube;
+            mirrored[index] = -mirrored[index];
+            pending.push(mirrored);
+            if index + 1 < N {
+                let mut swapped = cube;
+                swapped.swap(index, index + 1);
+                pending.push(swapped);
+            }
+        }
+    }
+    images
+}
+
+/// The cube standing for all the cubes this one can be mapped to.
+fn canonical<const N: usize>(mut cube: [i32; N]) -> [i32; N] {
+    for coord in cube[2..].iter_mut() {
+        *coord = coord.abs();
+    }
+    cube[2..].sort_unstable();
+    cube
+}
+
+/// The number of cubes a canonical cube stands for: every sign of its
+/// non-zero coordinates after the first two, and every distinct order of
+/// those coordinates.
+fn weight<const N: usize>(cube: &[i32; N]) -> usize {
+    let extra = &cube[2..];
+    let signs = 1 << extra.iter().filter(|&&coord| coord != 0).count();
+    let mut orders = factorial(extra.len());
+    // The coordinates are sorted, so equal ones are next to each other
+    let mut start = 0;
+    while start < extra.len() {
+        let end = start
+            + extra[start..]
+                .iter()
+                .take_while(|&&coord| coord == extra[start])
+                .count();
+        orders /= factorial(end - start);
+        start = end;
+    }
+    signs * orders
+}
+
+fn factorial(n: usize) -> usize {
+    (1..=n).product()
+}
@@ -0,0 +1,80 @@
+use day17::{
+    conway_rule, parse_input, pocket, render, simulate_in, Day17, Mode,
+};
+use std::fs::read_to_string;
+use std::path::Path;
+
+common::sample_tests!(Day17);
+
+#[test]
+fn higher_dimensions() {
+    let input = read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("samples/example.txt"),
+    )
+    .unwrap();
+    let slice = parse_input(&input).unwrap();
+    assert_eq!(
+        simulate_in(&slice, 3, &conway_rule(), 6, Mode::Full),
+        Ok(112)
+    );
+    assert_eq!(
+        simulate_in(&slice, 5, &conway_rule(), 6, Mode::Full),
+        Ok(5760)
+    );
+    assert!(simulate_in(&slice, 1, &conway_rule(), 6, Mode::Full).is_err());
+    // A cube has 26 neighbours in 3 dimensions, and 80 in 4
+    let rule = "B3,30/S2,3".parse().unwrap();
+    assert!(simulate_in(&slice, 3, &rule, 6, Mode::Full).is_err());
+    assert!(simulate_in(&slice, 4, &rule, 6, Mode::Full).is_ok());
+}
+
+#[test]
+fn symmetric() {
+    let input = read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("samples/example.txt"),
+    )
+    .unwrap();
+    let slice = parse_input(&input).unwrap();
+    for &(dimensions, active) in &[(2, 5), (3, 112), (4, 848), (5, 5760)] {
+        assert_eq!(
+            simulate_in(&slice, dimensions, &conway_rule(), 6, Mode::Symmetric),
+            Ok(active)
+        );
+    }
+}
+
+#[test]
+fn render_symmetric() {
+    let input = read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("samples/example.txt"),
+    )
+    .unwrap();
+    let slice = parse_input(&input).unwrap();
+    let mut full = pocket(&slice, 4, &conway_rule(), Mode::Full).unwrap();
+    let mut symmetric =
+        pocket(&slice, 4, &conway_rule(), Mode::Symmetric).unwrap();
+    for _ in 0..3 {
+        full.cycle();
+        symmetric.cycle();
+    }
+    assert_eq!(
+        render(&full.active_cubes()),
+        render(&symmetric.active_cubes())
+    );
+}
+
+#[test]
+fn render_slices() {
+    let input = read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("samples/example.txt"),
+    )
+    .unwrap();
+    let slice = parse_input(&input).unwrap();
+    let mut pocket = pocket(&slice, 3, &conway_rule(), Mode::Full).unwrap();
+    pocket.cycle();
+    // As shown in the puzzle after 1 cycle
+    assert_eq!(
+        render(&pocket.active_cubes()),
+        "z=-1\n#..\n..#\n.#.\n\nz=0\n#.#\n.##\n.#.\n\nz=1\n#..\n..#\n.#.\n"
+    );
+}
@@ -1,23 +1,22 @@
+use common::{parse_lines_with, Error, ErrorKind, ParseError, Solution};
 use std::collections::VecDeque;
 
-pub fn part1(expressions: &[String]) -> Result<u64, String> {
+pub fn part1(expressions: &[String]) -> Result<u64, ParseError> {
     sum_all(expressions, true)
 }
 
-pub fn part2(expressions: &[String]) -> Result<u64, String> {
+pub fn part2(expressions: &[String]) -> Result<u64, ParseError> {
     sum_all(expressions, false)
 }
 
 fn sum_all(
     expressions: &[String],
     same_precedence: bool,
-) -> Result<u64, String> {
+) -> Result<u64, ParseError> {
     let mut sum = 0;
-    for expression in expressions {
+    for (expression, line_num) in expressions.iter().zip(1..) {
         sum += parse(expression, same_precedence)
-            .map_err(|err| {
-                format!("Failed to parse expression '{}': {}", expression, err)
-            })?
+            .map_err(|err| err.at_line(line_num))?
             .eval()
     }
     Ok(sum)
@@ -60,7 +59,7 @@ enum Token {
     LeftParenthesis,
 }
 
-fn parse(s: &str, same_precedence: bool) -> Result<Expression, String> {
+fn parse(s: &str, same_precedence: bool) -> Result<Expression, ParseError> {
     let mut output: VecDeque<Expression> = VecDeque::new();
     let mut stack: VecDeque<Token> = VecDeque::new();
 
@@ -82,21 +81,23 @@ fn parse(s: &str, same_precedence: bool) -> Result<Expression, String> {
             ')' => {
                 process_stack(&mut stack, &mut output)?;
                 if stack.pop_back() != Some(Token::LeftParenthesis) {
-                    return Err(format!(
-                        "Unbalanced parenthesis at position {}",
-                        pos
-                    ));
+                    return Err(ParseError::new(ErrorKind::InvalidValue(
+                        "unbalanced parenthesis",
+                    ))
+                    .with_token(ch)
+                    .with_column(pos));
                 }
             }
-            d if d.is_digit(10) => {
+            d if d.is_ascii_digit() => {
                 let digit = d.to_digit(10).unwrap();
                 output.push_back(Expression::Value(digit.into()));
             }
             _ => {
-                return Err(format!(
-                    "Unexpected character '{}' at position {}",
-                    ch, pos
+                return Err(ParseError::new(ErrorKind::InvalidCharacter(
+                    "expression",
                 ))
+                .with_token(ch)
+                .with_column(pos))
             }
         }
     }
@@ -104,14 +105,14 @@ fn parse(s: &str, same_precedence: bool) -> Result<Expression, String> {
 
     match (output.pop_back(), output.pop_back(), stack.pop_back()) {
         (Some(expr), None, None) => Ok(expr),
-        _ => Err("Invalid expression".to_string()),
+        _ => Err(invalid_expression()),
     }
 }
 
 fn process_stack(
     stack: &mut VecDeque<Token>,
     output: &mut VecDeque<Expression>,
-) -> Result<(), String> {
+) -> Result<(), ParseError> {
     while let Some(token) = stack.pop_back() {
         match token {
             Token::Operator(oper) => {
@@ -124,7 +125,7 @@ fn process_stack(
                         ));
                     }
                     _ => {
-                        return Err("Invalid expression".to_string());
+                        return Err(invalid_expression());
                     }
                 }
             }
@@ -137,6 +138,35 @@ fn process_stack(
     Ok(())
 }
 
+fn invalid_expression() -> ParseError {
+    ParseError::new(ErrorKind::InvalidSyntax("expression"))
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    parse_lines_with(input, |line| parse(line, true).map(|_| line.to_string()))
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        part1(input).map_err(Error::Parse)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        part2(input).map_err(Error::Parse)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
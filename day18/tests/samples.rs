@@ -0,0 +1 @@
+common::sample_tests!(day18::Day18);
@@ -1,6 +1,9 @@
 #[macro_use]
 extern crate lazy_static;
 
+use common::{
+    column_of, parse_number, sections, Error, ErrorKind, ParseError, Solution,
+};
 use regex::{Match, Regex};
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -62,9 +65,7 @@ impl<'a> PatternMatcher<'a> {
         let chars = message.chars().collect::<Vec<_>>();
         self.match_rule(0, &chars, &[0])
             .map_err(|num| format!("Missing rule {}", num))
-            .map(|matches| {
-                matches.iter().any(|&match_len| match_len == message.len())
-            })
+            .map(|matches| matches.contains(&message.len()))
     }
 
     fn match_rule(
@@ -75,7 +76,7 @@ impl<'a> PatternMatcher<'a> {
     ) -> Result<Vec<usize>, RuleNumber> {
         let mut matches = Vec::new();
 
-        match self.rules.get(&rule_num).ok_or_else(|| rule_num)? {
+        match self.rules.get(&rule_num).ok_or(rule_num)? {
             Pattern::Terminal(ch) => {
                 for &position in positions {
                     if chars.get(position) == Some(ch) {
@@ -85,18 +86,18 @@ impl<'a> PatternMatcher<'a> {
             }
             Pattern::Sequence(rules) => {
                 for &position in positions {
-                    matches.extend(
-                        self.match_rule_seq(rules, chars, position)?.drain(..),
+                    matches.append(
+                        &mut self.match_rule_seq(rules, chars, position)?,
                     );
                 }
             }
             Pattern::Alternative(rules1, rules2) => {
                 for &position in positions {
-                    matches.extend(
-                        self.match_rule_seq(rules1, chars, position)?.drain(..),
+                    matches.append(
+                        &mut self.match_rule_seq(rules1, chars, position)?,
                     );
-                    matches.extend(
-                        self.match_rule_seq(rules2, chars, position)?.drain(..),
+                    matches.append(
+                        &mut self.match_rule_seq(rules2, chars, position)?,
                     );
                 }
             }
@@ -120,25 +121,24 @@ impl<'a> PatternMatcher<'a> {
 }
 
 impl FromStr for Rule {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut rule = s.split(": ");
-        let number = rule
-            .next()
-            .ok_or_else(|| "Invalid rule.".to_string())?
-            .parse()
-            .map_err(|err| format!("Invalid rule number: {}", err))?;
-        let pattern = rule
-            .next()
-            .ok_or_else(|| "Missing pattern.".to_string())?
-            .parse()?;
+        let number = parse_number(rule.next().unwrap(), "rule number")
+            .map_err(|err| err.with_column(1))?;
+        let pattern_str = rule.next().ok_or_else(|| {
+            ParseError::new(ErrorKind::Missing("pattern")).with_token(s)
+        })?;
+        let pattern = pattern_str.parse().map_err(|err: ParseError| {
+            err.at_column(column_of(s, pattern_str))
+        })?;
         Ok(Rule { number, pattern })
     }
 }
 
 impl FromStr for Pattern {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
@@ -155,14 +155,15 @@ impl FromStr for Pattern {
                 .as_str()
                 .split_whitespace()
                 .map(|num| {
-                    num.parse().map_err(|err| {
-                        format!("Invalid rule number '{}': {}", num, err)
-                    })
+                    parse_number(num, "rule number")
+                        .map_err(|err| err.with_column(column_of(s, num)))
                 })
                 .collect::<Result<Vec<u32>, _>>()
         };
 
-        let captures = REGEX.captures(s).ok_or_else(|| "Invalid pattern")?;
+        let captures = REGEX.captures(s).ok_or_else(|| {
+            ParseError::new(ErrorKind::InvalidSyntax("pattern")).with_token(s)
+        })?;
         if let Some(capture) = captures.name("numbers") {
             Ok(Self::Sequence(parse(capture)?))
         } else if let Some(capture) = captures.name("char") {
@@ -175,3 +176,54 @@ impl FromStr for Pattern {
         }
     }
 }
+
+pub fn parse_input(
+    input: &str,
+) -> Result<(Vec<Rule>, Vec<String>), ParseError> {
+    let sections = sections(input);
+    let (rules, messages) = match sections.as_slice() {
+        [rules, messages] => (rules, messages),
+        _ => {
+            return Err(ParseError::new(ErrorKind::Expected(
+                "rules and messages sections",
+            )))
+        }
+    };
+
+    let rules = rules
+        .lines()
+        .map(|(line, line_num)| {
+            line.parse()
+                .map_err(|err: ParseError| err.at_line(line_num))
+        })
+        .collect::<Result<_, _>>()?;
+    let messages = messages
+        .lines()
+        .map(|(line, _)| line.trim().to_string())
+        .collect();
+
+    Ok((rules, messages))
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+    type Input = (Vec<Rule>, Vec<String>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        let (rules, messages) = input;
+        part1(rules, messages).map_err(Error::Unsolvable)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        let (rules, messages) = input;
+        part2(rules, messages).map_err(Error::Unsolvable)
+    }
+}
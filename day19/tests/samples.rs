@@ -0,0 +1 @@
+common::sample_tests!(day19::Day19);
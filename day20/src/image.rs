@@ -1,4 +1,6 @@
 use super::*;
+use common::grid::{parse_grid, Grid, Point, SparseGrid};
+use common::ParseError;
 use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::fmt::{Display, Formatter};
@@ -22,7 +24,7 @@ impl Image {
             return Err("Image requires a square number of tiles".to_string());
         }
 
-        let tile_dim = tiles.get(0).unwrap().image.x_dimension;
+        let tile_dim = tiles.first().unwrap().image.x_dimension;
         let x_dimension = tile_dim * tile_mult;
         let y_dimension = x_dimension;
         let on_pixels = HashSet::new();
@@ -185,44 +187,29 @@ impl Image {
 
 impl Display for Image {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for y_coord in 0..self.y_dimension {
-            for x_coord in 0..self.x_dimension {
-                if self.on_pixels.contains(&Position::new(x_coord, y_coord)) {
-                    write!(f, "#")?;
-                } else {
-                    write!(f, ".")?;
-                }
-            }
-            writeln!(f)?;
+        let mut grid = SparseGrid::new(
+            self.x_dimension as usize,
+            self.y_dimension as usize,
+        );
+        for pos in self.on_pixels.iter() {
+            grid.set(Point::new(pos.x_coord as i32, pos.y_coord as i32), true);
         }
-        Ok(())
+        write!(f, "{}", grid)
     }
 }
 
 impl FromStr for Image {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut x_dimension = 0;
-        let y_dimension = s.lines().count();
-        let on_pixels = s
-            .lines()
-            .zip(0..)
-            .flat_map(|(line, y_coord)| {
-                x_dimension = x_dimension.max(line.chars().count());
-                line.chars().zip(0..).filter(|(ch, _)| *ch != '.').map(
-                    move |(ch, x_coord)| match ch {
-                        '#' => Ok(Position::new(x_coord, y_coord)),
-                        _ => {
-                            Err(format!("Invalid character in image '{}'", ch))
-                        }
-                    },
-                )
-            })
-            .collect::<Result<HashSet<_>, _>>()?;
+        let grid: SparseGrid<bool> = parse_grid(s, "image")?;
+        let on_pixels = grid
+            .cells()
+            .map(|(point, _)| Position::new(point.x as u32, point.y as u32))
+            .collect();
         Ok(Self {
-            x_dimension: x_dimension.try_into().unwrap(),
-            y_dimension: y_dimension.try_into().unwrap(),
+            x_dimension: grid.width().try_into().unwrap(),
+            y_dimension: grid.height().try_into().unwrap(),
             on_pixels,
         })
     }
@@ -4,6 +4,7 @@ extern crate lazy_static;
 pub mod image;
 pub mod tile;
 
+use common::{sections, Error, ParseError, Solution};
 use image::Image;
 use tile::Tile;
 
@@ -27,7 +28,8 @@ pub fn part2(tiles: &[Tile]) -> Result<usize, String> {
         "#....##....##....###\n",
         ".#..#..#..#..#..#...\n",
     )
-    .parse()?;
+    .parse()
+    .map_err(|err: ParseError| err.to_string())?;
 
     let transformations = [
         Image::rotate_left,
@@ -99,3 +101,36 @@ impl Direction {
         }
     }
 }
+
+pub fn parse_input(input: &str) -> Result<Vec<Tile>, ParseError> {
+    sections(input)
+        .iter()
+        .map(|section| {
+            section
+                .text
+                .parse()
+                .map_err(|err: ParseError| err.at_line(section.line_num))
+        })
+        .collect()
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+    type Input = Vec<Tile>;
+    type Answer1 = u64;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        part1(input).ok_or(Error::NotFound)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        part2(input).map_err(Error::Unsolvable)
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use common::{parse_number, ErrorKind, ParseError};
 use regex::Regex;
 use std::str::FromStr;
 
@@ -167,7 +168,7 @@ impl Tile {
     }
 
     pub fn align(&self, direction: Direction, border: &Border) -> Option<Self> {
-        let (align_dir, flip) = self.border_align(&border)?;
+        let (align_dir, flip) = self.border_align(border)?;
         let rotated = match align_dir.rotate(direction) {
             Rotation::Nil => self.clone(),
             Rotation::Left => self.rotate_left(),
@@ -186,7 +187,7 @@ impl Tile {
 }
 
 impl FromStr for Tile {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
@@ -194,27 +195,38 @@ impl FromStr for Tile {
                 Regex::new(r"^Tile (?P<id>\d+):\n(?P<pixels>[\.#\n\r]+)$")
                     .unwrap();
         }
-        let captures = REGEX.captures(s).ok_or_else(|| "Invalid tile")?;
-        let id = captures
-            .name("id")
+        let captures = REGEX.captures(s).ok_or_else(|| {
+            ParseError::new(ErrorKind::InvalidSyntax("tile"))
+                .with_token(s.lines().next().unwrap_or_default())
+        })?;
+        let id_match = captures.name("id").unwrap();
+        let id = parse_number(id_match.as_str(), "tile ID")
+            .map_err(|err| err.at_line(1).with_column(id_match.start() + 1))?;
+        let mut image: Image = captures
+            .name("pixels")
             .unwrap()
             .as_str()
             .parse()
-            .map_err(|err| format!("Invalid tile ID: {}", err))?;
-        let mut image: Image =
-            captures.name("pixels").unwrap().as_str().parse()?;
+            .map_err(|err: ParseError| err.at_line(2))?;
 
+        let size = format!("{}x{}", image.x_dimension, image.y_dimension);
         if image.x_dimension != image.y_dimension {
-            return Err(format!("Tile {} is not square", id));
+            return Err(ParseError::new(ErrorKind::InvalidValue(
+                "tile is not square",
+            ))
+            .with_token(size));
         }
         if image.x_dimension < 3 {
-            return Err(format!("Tle {} is too small", id));
+            return Err(ParseError::new(ErrorKind::InvalidValue(
+                "tile is too small",
+            ))
+            .with_token(size));
         }
         if image.x_dimension > MAX_DIMENSION {
-            return Err(format!(
-                "Tile {} exceeds maximum dimension of {}",
-                id, MAX_DIMENSION
-            ));
+            return Err(ParseError::new(ErrorKind::InvalidValue(
+                "tile exceeds maximum dimension",
+            ))
+            .with_token(size));
         }
 
         let dimension = image.x_dimension;
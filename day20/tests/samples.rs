@@ -0,0 +1 @@
+common::sample_tests!(day20::Day20);
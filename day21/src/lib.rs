@@ -1,6 +1,7 @@
 #[macro_use]
 extern crate lazy_static;
 
+use common::{parse_lines, Error, ErrorKind, ParseError, Solution};
 use regex::Regex;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::str::FromStr;
@@ -97,7 +98,7 @@ pub fn part2(foods: &[Food]) -> Option<String> {
 }
 
 impl FromStr for Food {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
@@ -106,7 +107,9 @@ impl FromStr for Food {
             )
             .unwrap();
         }
-        let captures = REGEX.captures(s).ok_or_else(|| "Invalid food")?;
+        let captures = REGEX.captures(s).ok_or_else(|| {
+            ParseError::new(ErrorKind::InvalidSyntax("food")).with_token(s)
+        })?;
         let ingredients = captures
             .name("ingredients")
             .unwrap()
@@ -127,3 +130,28 @@ impl FromStr for Food {
         })
     }
 }
+
+pub fn parse_input(input: &str) -> Result<Vec<Food>, ParseError> {
+    parse_lines(input)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+    type Input = Vec<Food>;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        part1(input).ok_or(Error::NotFound)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        part2(input).ok_or(Error::NotFound)
+    }
+}
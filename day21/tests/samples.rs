@@ -0,0 +1 @@
+common::sample_tests!(day21::Day21);
@@ -1,3 +1,7 @@
+use common::{
+    column_of, parse_number, sections, Error, ErrorKind, ParseError, Section,
+    Solution,
+};
 use std::collections::{HashSet, VecDeque};
 
 pub type Cards = VecDeque<usize>;
@@ -84,3 +88,63 @@ pub fn part2(cards1: &Cards, cards2: &Cards) -> usize {
         Winner::Player2 => score(&player2),
     }
 }
+
+pub fn parse_input(input: &str) -> Result<(Cards, Cards), ParseError> {
+    match sections(input).as_slice() {
+        [deck1, deck2] => Ok((
+            parse_deck(deck1, "Player 1:")?,
+            parse_deck(deck2, "Player 2:")?,
+        )),
+        _ => Err(ParseError::new(ErrorKind::Expected("two decks of cards"))),
+    }
+}
+
+fn parse_deck(
+    section: &Section,
+    header: &'static str,
+) -> Result<Cards, ParseError> {
+    let mut lines = section.lines();
+    match lines.next() {
+        Some((line, _)) if line.trim() == header => (),
+        Some((line, line_num)) => {
+            return Err(ParseError::new(ErrorKind::Expected(header))
+                .with_token(line)
+                .at_line(line_num))
+        }
+        None => {
+            return Err(ParseError::new(ErrorKind::Expected(header))
+                .at_line(section.line_num))
+        }
+    }
+    lines
+        .map(|(line, line_num)| {
+            let card = line.trim();
+            parse_number(card, "card").map_err(|err| {
+                err.at_line(line_num).with_column(column_of(line, card))
+            })
+        })
+        .collect()
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u32 = 22;
+    type Input = (Cards, Cards);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        let (cards1, cards2) = input;
+        Ok(part1(cards1, cards2))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        let (cards1, cards2) = input;
+        Ok(part2(cards1, cards2))
+    }
+}
@@ -0,0 +1 @@
+common::sample_tests!(day22::Day22);
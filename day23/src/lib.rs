@@ -1,3 +1,4 @@
+use common::{Error, ErrorKind, ParseError, Solution};
 use std::str::FromStr;
 
 #[derive(Clone)]
@@ -88,21 +89,27 @@ pub fn part2(cups: &Cups) -> usize {
 }
 
 impl FromStr for Cups {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut labels = s
-            .trim()
+            .trim_end()
             .chars()
-            .map(|ch| {
-                ch.to_digit(10)
-                    .map(|digit| digit as usize)
-                    .ok_or_else(|| format!("Invalid label for cup '{}'", ch))
+            .zip(1..)
+            .map(|(ch, column)| {
+                ch.to_digit(10).map(|digit| digit as usize).ok_or_else(|| {
+                    ParseError::new(ErrorKind::InvalidCharacter("cup labels"))
+                        .with_token(ch)
+                        .with_column(column)
+                })
             })
-            .collect::<Result<Vec<usize>, String>>()?;
+            .collect::<Result<Vec<usize>, _>>()?;
 
         // Assuming there are no gaps, no repetitions, no zeros
-        let max = *labels.iter().max().ok_or_else(|| "No label found")?;
+        let max = *labels
+            .iter()
+            .max()
+            .ok_or_else(|| ParseError::new(ErrorKind::Missing("cup labels")))?;
         let current = labels[0];
         let mut next = vec![0; labels.len() + 1];
         for window in labels.windows(2) {
@@ -115,3 +122,28 @@ impl FromStr for Cups {
         Ok(Self { max, current, next })
     }
 }
+
+pub fn parse_input(input: &str) -> Result<Cups, ParseError> {
+    input.parse().map_err(|err: ParseError| err.at_line(1))
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+    type Input = Cups;
+    type Answer1 = String;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part2(input))
+    }
+}
@@ -0,0 +1 @@
+common::sample_tests!(day23::Day23);
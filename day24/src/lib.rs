@@ -1,4 +1,8 @@
-use std::collections::{HashMap, HashSet};
+pub mod render;
+
+use common::automaton::{Automaton, Hexagonal, Rule, HEX_DIRECTIONS};
+use common::{parse_lines_with, Error, ErrorKind, ParseError, Solution};
+use std::collections::HashSet;
 
 #[derive(Clone, Copy)]
 pub enum Direction {
@@ -10,121 +14,72 @@ pub enum Direction {
     NorthEast,
 }
 
-pub type Instruction = Vec<Direction>;
-
-#[derive(Clone, Default, Eq, Hash, PartialEq)]
-struct HexPosition {
-    horizontal: i32,
-    vertical: i32,
+impl Direction {
+    /// The step to the adjacent tile, in axial coordinates
+    fn delta(self) -> (i32, i32) {
+        HEX_DIRECTIONS[self as usize]
+    }
 }
 
-impl HexPosition {
-    fn step(&self, direction: Direction) -> Self {
-        let (horizontal_shift, vertical_shift) = match direction {
-            Direction::East => (2, 0),
-            Direction::SouthEast => (1, 1),
-            Direction::NorthEast => (1, -1),
-            Direction::West => (-2, 0),
-            Direction::SouthWest => (-1, 1),
-            Direction::NorthWest => (-1, -1),
-        };
-
-        Self {
-            horizontal: self.horizontal + horizontal_shift,
-            vertical: self.vertical + vertical_shift,
-        }
-    }
+pub type Instruction = Vec<Direction>;
 
-    fn neighbours(&self) -> impl Iterator<Item = Self> + '_ {
-        [
-            Direction::East,
-            Direction::SouthEast,
-            Direction::SouthWest,
-            Direction::West,
-            Direction::NorthWest,
-            Direction::NorthEast,
-        ]
-        .iter()
-        .map(move |direction| self.step(*direction))
+/// Follows every instruction from the reference tile and flips the tile
+/// reached, returning the tiles left black.
+fn black_tiles(instructions: &[Instruction]) -> HashSet<(i32, i32)> {
+    let mut black_tiles = HashSet::new();
+    for instruction in instructions {
+        let tile = instruction.iter().fold((0, 0), |(q, r), direction| {
+            let (delta_q, delta_r) = direction.delta();
+            (q + delta_q, r + delta_r)
+        });
+        if !black_tiles.remove(&tile) {
+            black_tiles.insert(tile);
+        }
     }
+    black_tiles
 }
 
-#[derive(Default)]
-struct Grid {
-    black_tiles: HashSet<HexPosition>,
+pub fn part1(instructions: &[Instruction]) -> usize {
+    black_tiles(instructions).len()
 }
 
-impl Grid {
-    fn flip_tile(&mut self, instruction: &[Direction]) {
-        let mut position = HexPosition::default();
-        for direction in instruction {
-            position = position.step(*direction);
-        }
-        if !self.black_tiles.remove(&position) {
-            self.black_tiles.insert(position);
-        }
-    }
-
-    fn count_black(&self) -> usize {
-        self.black_tiles.len()
-    }
+pub fn part2(instructions: &[Instruction]) -> usize {
+    simulate(instructions, &flip_rule(), 100)
+}
 
-    fn update(&mut self) {
-        let mut white_tiles: HashMap<HexPosition, usize> = HashMap::new();
-        let remain_black = self
-            .black_tiles
-            .iter()
-            .filter(|&tile| {
-                let mut black_neighbours = 0;
-                for neighbour in tile.neighbours() {
-                    if self.black_tiles.contains(&neighbour) {
-                        black_neighbours += 1;
-                    } else {
-                        white_tiles
-                            .entry(neighbour)
-                            .and_modify(|black_neighbours| {
-                                *black_neighbours += 1
-                            })
-                            .or_insert(1);
-                    }
-                }
-                black_neighbours == 1 || black_neighbours == 2
-            })
-            .cloned()
-            .collect();
-        self.black_tiles = remain_black;
-        self.black_tiles.extend(
-            white_tiles
-                .drain()
-                .filter(|(_, black_neighbours)| *black_neighbours == 2)
-                .map(|(position, _)| position),
-        );
-    }
+/// The rule of the daily flips: black tiles with 1 or 2 black neighbours
+/// stay black, and white tiles with exactly 2 black neighbours are flipped
+/// to black.
+pub fn flip_rule() -> Rule {
+    Rule::new(&[2], &[1, 2])
 }
 
-pub fn part1(instructions: &[Instruction]) -> usize {
-    let mut grid = Grid::default();
-    for instruction in instructions {
-        grid.flip_tile(instruction);
-    }
-    grid.count_black()
+/// Flips the tiles daily for the given number of days, starting with the
+/// tiles flipped by the instructions, and returns the number of black tiles.
+pub fn simulate(
+    instructions: &[Instruction],
+    rule: &Rule,
+    days: usize,
+) -> usize {
+    let mut floor = floor(instructions, rule);
+    floor.run(days);
+    floor.count_active()
 }
 
-pub fn part2(instructions: &[Instruction]) -> usize {
-    let mut grid = Grid::default();
-    for instruction in instructions {
-        grid.flip_tile(instruction);
-    }
-    for _ in 1..=100 {
-        grid.update();
-    }
-    grid.count_black()
+/// The floor before any day, with the tiles flipped by the instructions as
+/// the active cells, to be flipped daily with the given rule.
+pub fn floor(
+    instructions: &[Instruction],
+    rule: &Rule,
+) -> Automaton<Hexagonal> {
+    Automaton::new(Hexagonal, rule.clone(), black_tiles(instructions))
 }
 
-pub fn parse_instruction(s: &str) -> Result<Instruction, String> {
+pub fn parse_instruction(s: &str) -> Result<Instruction, ParseError> {
     let mut instr = Vec::new();
-    let mut chars = s.chars().peekable();
-    while let (Some(ch), next) = (chars.next(), chars.peek()) {
+    let mut chars = s.chars().zip(1..).peekable();
+    while let (Some((ch, column)), next) = (chars.next(), chars.peek()) {
+        let next = next.map(|(next_ch, _)| next_ch);
         match ch {
             'e' => instr.push(Direction::East),
             'w' => instr.push(Direction::West),
@@ -144,8 +99,39 @@ pub fn parse_instruction(s: &str) -> Result<Instruction, String> {
                 chars.next();
                 instr.push(Direction::SouthWest)
             }
-            _ => return Err(format!("Invalid direction '{}'", ch)),
+            _ => {
+                return Err(ParseError::new(ErrorKind::InvalidSyntax(
+                    "direction",
+                ))
+                .with_token(ch)
+                .with_column(column))
+            }
         }
     }
     Ok(instr)
 }
+
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines_with(input, parse_instruction)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+    type Input = Vec<Instruction>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part2(input))
+    }
+}
@@ -1,45 +1,133 @@
-use clap::{crate_description, App, Arg};
-use day24::*;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use clap::{crate_description, Arg, ArgMatches, Error, ErrorKind};
+use common::automaton::{Hexagonal, Rule};
+use common::runner;
+use day24::{flip_rule, floor, parse_input, render, Day24};
+use std::fs::{create_dir_all, write};
+use std::path::Path;
 use std::process::exit;
 
 fn main() {
-    let args = App::new(crate_description!())
+    let args = runner::app(crate_description!())
         .arg(
-            Arg::with_name("INPUT")
-                .help("File with puzzle input")
-                .required(true)
-                .index(1),
+            Arg::with_name("rule")
+                .long("rule")
+                .value_name("RULE")
+                .validator(|value| match value.parse::<Rule>() {
+                    Ok(rule) if rule.next_state(false, 0) => {
+                        Err("tiles can't be flipped to black with no black \
+                         neighbours"
+                            .to_string())
+                    }
+                    Ok(rule) => rule.check(&Hexagonal),
+                    Err(err) => Err(err.to_string()),
+                })
+                .conflicts_with_all(&["format", "part", "check"])
+                .help(
+                    "Flips the tiles with another rule, e.g. B2/S12 for \
+                     white tiles to turn black with 2 black neighbours and \
+                     black tiles to stay black with 1 or 2",
+                ),
+        )
+        .arg(
+            Arg::with_name("days")
+                .long("days")
+                .value_name("N")
+                .validator(|value| {
+                    value
+                        .parse::<usize>()
+                        .map(|_| ())
+                        .map_err(|_| "must be a number".to_string())
+                })
+                .conflicts_with_all(&["format", "part", "check"])
+                .help("Flips the tiles for this many days instead of 100"),
+        )
+        .arg(
+            Arg::with_name("render")
+                .long("render")
+                .value_name("FORMAT")
+                .possible_values(&["text", "svg"])
+                .conflicts_with_all(&["format", "part", "check"])
+                .help("Draws the floor before and after every day"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .value_name("DIR")
+                .requires("render")
+                .required_if("render", "svg")
+                .help("Directory where SVG drawings are written, one per day"),
         )
         .get_matches();
 
-    println!(crate_description!());
-
-    let input = match read_input(args.value_of("INPUT").unwrap()) {
-        Ok(data) => data,
-        Err(err) => {
-            println!("Failed to read input: {}", err);
-            exit(2);
-        }
-    };
+    if args.is_present("output") && args.value_of("render") != Some("svg") {
+        Error::with_description(
+            "--output only applies to drawings rendered as SVG",
+            ErrorKind::ArgumentConflict,
+        )
+        .exit();
+    }
 
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    if ["rule", "days", "render"]
+        .iter()
+        .any(|&name| args.is_present(name))
+    {
+        simulate(&args);
+    } else {
+        runner::run::<Day24>(&args);
+    }
 }
 
-fn read_input(filename: &str) -> Result<Vec<Instruction>, String> {
-    let input_file = File::open(filename).map_err(|err| err.to_string())?;
+/// Flips the tiles of every input as requested on the command line.
+fn simulate(args: &ArgMatches) {
+    let rule = args
+        .value_of("rule")
+        .map_or_else(flip_rule, |rule| rule.parse().unwrap());
+    let days = args
+        .value_of("days")
+        .map_or(100, |days| days.parse().unwrap());
+    let inputs = runner::input_args(args);
+    for (index, &filename) in inputs.iter().enumerate() {
+        let instructions = runner::parse_file(filename, parse_input);
+        let instructions = instructions.unwrap_or_else(|err| {
+            println!("{}: {}", filename, err);
+            exit(2);
+        });
 
-    BufReader::new(input_file)
-        .lines()
-        .zip(1..)
-        .map(|(line, line_num)| {
-            line.map_err(|err| (line_num, err.to_string()))
-                .and_then(|value| {
-                    parse_instruction(&value).map_err(|err| (line_num, err))
-                })
-        })
-        .collect::<Result<_, _>>()
-        .map_err(|(line_num, err)| format!("Line {}: {}", line_num, err))
+        let mut floor = floor(&instructions, &rule);
+        for day in 0..=days {
+            if day > 0 {
+                floor.step();
+            }
+            match args.value_of("render") {
+                Some("text") => {
+                    println!("{}: day {}:\n", filename, day);
+                    println!("{}", render::text(floor.active()));
+                }
+                Some(_) => {
+                    let dir = Path::new(args.value_of("output").unwrap());
+                    // Keep the drawings of each input apart
+                    let name = if inputs.len() > 1 {
+                        format!("input{}-day{:03}.svg", index + 1, day)
+                    } else {
+                        format!("day{:03}.svg", day)
+                    };
+                    let written = create_dir_all(dir).and_then(|_| {
+                        write(dir.join(name), render::svg(floor.active()))
+                    });
+                    if let Err(err) = written {
+                        println!("Failed to write drawing: {}", err);
+                        exit(2);
+                    }
+                }
+                None => (),
+            }
+        }
+        println!(
+            "{}: {} black tiles after {} days with rule {}",
+            filename,
+            floor.count_active(),
+            days,
+            rule
+        );
+    }
 }
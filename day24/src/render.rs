@@ -0,0 +1,104 @@
+//! Drawings of the floor, with tiles given in axial coordinates `(q, r)`.
+//!
+//! Rows of tiles are offset by half a tile from one another, with `q` growing
+//! to the east along a row and `r` growing by one row to the south-east.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Horizontal position of a tile's centre, in half tiles
+fn column((q, r): (i32, i32)) -> i32 {
+    2 * q + r
+}
+
+/// Writes the tiles as text, one row per line, with `#` for black tiles
+/// and `.` for white ones. Tiles in a row are separated by a space, so that
+/// each row is shifted by one character from the one above.
+pub fn text(black_tiles: &HashSet<(i32, i32)>) -> String {
+    if black_tiles.is_empty() {
+        return String::new();
+    }
+    let columns = black_tiles.iter().map(|&tile| column(tile));
+    let min_column = columns.clone().min().unwrap();
+    let max_column = columns.max().unwrap();
+    let min_r = black_tiles.iter().map(|&(_, r)| r).min().unwrap();
+    let max_r = black_tiles.iter().map(|&(_, r)| r).max().unwrap();
+
+    let mut text = String::new();
+    for r in min_r..=max_r {
+        let mut line = String::new();
+        for column in min_column..=max_column {
+            // Only every other column has a tile's centre in a row
+            if (column - r).rem_euclid(2) != 0 {
+                line.push(' ');
+            } else if black_tiles.contains(&((column - r) / 2, r)) {
+                line.push('#');
+            } else {
+                line.push('.');
+            }
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// Radius of a tile in an SVG drawing
+const SIZE: f64 = 10.0;
+
+/// Draws the black tiles as an SVG image of pointy-topped hexagons.
+pub fn svg(black_tiles: &HashSet<(i32, i32)>) -> String {
+    let width = 3f64.sqrt() * SIZE;
+    let centre = |(q, r): (i32, i32)| {
+        (
+            f64::from(column((q, r))) * width / 2.0,
+            f64::from(r) * SIZE * 1.5,
+        )
+    };
+    let centres: Vec<_> =
+        black_tiles.iter().map(|&tile| centre(tile)).collect();
+    // The view always includes the reference tile, at the origin
+    let min_x = centres.iter().map(|&(x, _)| x).fold(0.0, f64::min) - SIZE;
+    let max_x = centres.iter().map(|&(x, _)| x).fold(0.0, f64::max) + SIZE;
+    let min_y = centres.iter().map(|&(_, y)| y).fold(0.0, f64::min) - SIZE;
+    let max_y = centres.iter().map(|&(_, y)| y).fold(0.0, f64::max) + SIZE;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{:.1} {:.1} {:.1} \
+         {:.1}\">",
+        min_x,
+        min_y,
+        max_x - min_x,
+        max_y - min_y
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"100%\" height=\"100%\" \
+         fill=\"white\"/>",
+        min_x, min_y
+    )
+    .unwrap();
+    for (x, y) in centres {
+        let corners: Vec<_> = (0..6)
+            .map(|corner| {
+                let angle = f64::from(60 * corner + 30).to_radians();
+                format!(
+                    "{:.1},{:.1}",
+                    x + SIZE * angle.cos(),
+                    y + SIZE * angle.sin()
+                )
+            })
+            .collect();
+        writeln!(
+            svg,
+            "<polygon points=\"{}\" fill=\"black\" stroke=\"grey\"/>",
+            corners.join(" ")
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
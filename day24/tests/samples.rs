@@ -0,0 +1,29 @@
+use common::automaton::Rule;
+use day24::{flip_rule, parse_input, render, simulate, Day24};
+use std::collections::HashSet;
+use std::fs::read_to_string;
+use std::path::Path;
+
+common::sample_tests!(Day24);
+
+#[test]
+fn rule_string() {
+    let input = read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("samples/example.txt"),
+    )
+    .unwrap();
+    let instructions = parse_input(&input).unwrap();
+    let rule: Rule = "B2/S12".parse().unwrap();
+    assert_eq!(rule, flip_rule());
+    // Daily counts given in the puzzle
+    for &(days, black) in &[(1, 15), (2, 12), (10, 37), (100, 2208)] {
+        assert_eq!(simulate(&instructions, &rule, days), black);
+    }
+}
+
+#[test]
+fn render_text() {
+    let tiles: HashSet<_> =
+        vec![(0, 0), (1, 0), (0, 1), (-1, 2)].into_iter().collect();
+    assert_eq!(render::text(&tiles), "# #\n #\n# .\n");
+}
@@ -1,3 +1,6 @@
+use common::{parse_number, Error, ErrorKind, ParseError, Solution};
+use std::convert::Infallible;
+
 const SUBJECT_NUMBER: u64 = 7;
 const DIVISOR: u64 = 20201227;
 
@@ -22,3 +25,42 @@ fn transform(pubkey: u64, loop_size: u64) -> u64 {
 pub fn part1(pubkey1: u64, pubkey2: u64) -> u64 {
     transform(pubkey2, find_loop_size(pubkey1))
 }
+
+pub fn parse_input(input: &str) -> Result<(u64, u64), ParseError> {
+    let keys = input
+        .lines()
+        .zip(1..)
+        .take(2)
+        .map(|(line, line_num)| {
+            parse_number(line.trim(), "public key")
+                .map_err(|err| err.at_line(line_num))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    match keys.get(0..2) {
+        Some(&[key1, key2]) => Ok((key1, key2)),
+        _ => Err(ParseError::new(ErrorKind::Missing("public key"))),
+    }
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+    const PARTS: u32 = 1;
+    type Input = (u64, u64);
+    type Answer1 = u64;
+    type Answer2 = Infallible;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error> {
+        let (key1, key2) = *input;
+        Ok(part1(key1, key2))
+    }
+
+    fn part2(_input: &Self::Input) -> Result<Self::Answer2, Error> {
+        unreachable!("day 25 only has one part")
+    }
+}
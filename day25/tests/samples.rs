@@ -0,0 +1,17 @@
+use common::runner::{solve, Options};
+use day25::Day25;
+use std::fs::read_to_string;
+use std::path::Path;
+
+common::sample_tests!(Day25);
+
+#[test]
+fn single_part() {
+    let input = read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("samples/example.txt"),
+    )
+    .unwrap();
+    let parts = solve::<Day25>(&input, &Options::default()).unwrap();
+    assert_eq!(parts.len(), 1);
+    assert_eq!(parts[0].part, 1);
+}